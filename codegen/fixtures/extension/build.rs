@@ -0,0 +1,12 @@
+// build.rs
+fn main() {
+    println!("cargo::rerun-if-changed=my_elements.yml");
+    let generated = easy_svg_codegen::Generator::new()
+        .extension_file("my_elements.yml")
+        .expect("Failed to read my_elements.yml")
+        .generate()
+        .unwrap_or_else(|error| panic!("{}", error));
+    generated
+        .write_to(std::env::var("OUT_DIR").unwrap())
+        .expect("Failed to write generated code");
+}
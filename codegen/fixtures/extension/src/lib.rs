@@ -0,0 +1,32 @@
+//! The extension example of the easy-svg-codegen README, so that it is built and run against
+//! the real generator. Keep the two in sync.
+
+include!(concat!(env!("OUT_DIR"), "/generated.rs"));
+
+#[cfg(test)]
+mod tests {
+    use crate::elements::*;
+    use easy_svg::elements::*;
+
+    #[test]
+    fn test_readme_example() {
+        let gauge = Gauge::new()
+            .value(3.)
+            .add_child_rect(Rect::new(10., 2.).data_kind("track"));
+        let svg = Svg::new()
+            .width(100.)
+            .height(100.)
+            .add_child_shape_element(gauge);
+        assert_eq!(
+            svg.to_string(),
+            r#"<svg height="100" width="100"><gauge value="3"><rect height="2" width="10" data-kind="track"/></gauge></svg>"#
+        );
+    }
+
+    #[test]
+    fn test_readme_matches_fixture() {
+        let readme = include_str!("../../../README.md");
+        assert!(readme.contains(include_str!("../my_elements.yml")));
+        assert!(readme.contains(include_str!("../build.rs")));
+    }
+}
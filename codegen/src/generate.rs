@@ -0,0 +1,911 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::collections::BTreeSet;
+
+use crate::schema::{Config, Element, Field, KeywordVariant};
+
+pub(crate) fn generate_enums<'a>(
+    config: &'a Config,
+    enum_names: impl Iterator<Item = &'a String>,
+) -> TokenStream {
+    let enums = enum_names.map(|enum_name| {
+        let keyword_enum = &config.enums[enum_name];
+        let enum_ident = format_ident!("{}", enum_name);
+        let description = keyword_enum
+            .description
+            .iter()
+            .map(|description| format!(" {}", description));
+        let variant_idents = keyword_enum
+            .variants
+            .iter()
+            .map(|variant| format_ident!("{}", variant.name()))
+            .collect::<Vec<_>>();
+        let keywords = keyword_enum
+            .variants
+            .iter()
+            .map(KeywordVariant::keyword)
+            .collect::<Vec<_>>();
+        let variant_docs = keywords.iter().map(|keyword| format!(" `{}`", keyword));
+        let default_attributes = keyword_enum.variants.iter().map(|variant| {
+            if keyword_enum.default.as_deref() == Some(variant.keyword()) {
+                quote! { #[default] }
+            } else {
+                quote! {}
+            }
+        });
+        let default_derive = if keyword_enum.default.is_some() {
+            quote! { Default, }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            #( #[doc = #description] )*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, #default_derive Serialize, Deserialize)]
+            pub enum #enum_ident {
+                #(
+                    #[doc = #variant_docs]
+                    #default_attributes
+                    #[serde(rename = #keywords)]
+                    #variant_idents
+                ),*
+            }
+
+            impl #enum_ident {
+                /// Every keyword, as written in markup
+                pub const KEYWORDS: &'static [&'static str] = &[ #( #keywords ),* ];
+            }
+
+            impl std::fmt::Display for #enum_ident {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    let keyword = match self {
+                        #( #enum_ident::#variant_idents => #keywords ),*
+                    };
+                    write!(f, "{}", keyword)
+                }
+            }
+
+            impl std::str::FromStr for #enum_ident {
+                type Err = ParseKeywordError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    match s {
+                        #( #keywords => Ok(#enum_ident::#variant_idents), )*
+                        _ => Err(ParseKeywordError::new(s, Self::KEYWORDS)),
+                    }
+                }
+            }
+        }
+    });
+
+    quote! {
+        #( #enums )*
+    }
+}
+
+pub(crate) fn generate_category_traits<'a>(
+    categories: impl Iterator<Item = &'a String>,
+) -> TokenStream {
+    let category_traits = categories.map(|category| {
+        let trait_name = format_ident!("{}", category);
+        quote! {
+            pub trait #trait_name: Into<Shape> + Clone {}
+        }
+    });
+
+    quote! {
+        #( #category_traits )*
+    }
+}
+
+/// Built-in elements have their own `Shape` variant, extension elements are `Shape::Custom` nodes
+fn generate_shape_from(element_name: &str, is_builtin: bool) -> TokenStream {
+    let struct_name = capitalize(element_name);
+
+    let struct_name_ident = format_ident!("{}", &struct_name);
+    let element_name_ident = format_ident!("{}", camel_to_snake(element_name));
+
+    if is_builtin {
+        return quote! {
+            impl From<#struct_name_ident> for Shape {
+                fn from(#element_name_ident: #struct_name_ident) -> Self {
+                    Self::#struct_name_ident(#element_name_ident)
+                }
+            }
+        };
+    }
+
+    quote! {
+        impl From<#struct_name_ident> for Shape {
+            fn from(#element_name_ident: #struct_name_ident) -> Self {
+                Self::custom(#element_name_ident)
+            }
+        }
+
+        impl SvgNode for #struct_name_ident {
+            fn tag_name(&self) -> &str {
+                #struct_name_ident::tag_name(self)
+            }
+
+            fn attributes(&self) -> Attributes {
+                let mut attributes = Attributes::new();
+                for name in Self::ATTRIBUTES {
+                    if let Some(value) = self.get_attribute(name) {
+                        attributes
+                            .try_insert(name.to_string(), value, &[])
+                            .expect("attribute names come from the schema");
+                    }
+                }
+                for (name, value) in self.extra_attributes.iter() {
+                    attributes
+                        .try_insert(name.to_string(), value.to_string(), &[])
+                        .expect("extra attribute names are validated when set");
+                }
+                attributes
+            }
+
+            fn child_nodes(&self) -> &[Shape] {
+                #struct_name_ident::child_nodes(self)
+            }
+
+            fn write_svg(&self, svg: &mut String, options: &SerializeOptions) {
+                #struct_name_ident::write_svg(self, svg, options)
+            }
+
+            fn map_colors(&mut self, f: &mut dyn FnMut(&Color) -> Color) {
+                #struct_name_ident::map_colors(self, f)
+            }
+        }
+    }
+}
+
+/// The struct of an element along with its builders, serialization and `Shape` conversion
+pub(crate) fn generate_element(
+    name: &str,
+    element: &Element,
+    config: &Config,
+    is_builtin: bool,
+) -> TokenStream {
+    let struct_code = generate_struct(name, element);
+    let impl_code = generate_impl(name, element, config);
+    let to_string_code = generate_to_string(name, element, config);
+    let shape_from_code = generate_shape_from(name, is_builtin);
+
+    quote! {
+        #struct_code
+        #impl_code
+        #to_string_code
+        #shape_from_code
+    }
+}
+
+/// Typed builders for attributes an extension adds to a built-in element, which go through
+/// the element's `attr` since its struct can't gain fields
+pub(crate) fn generate_attribute_extension(
+    element_name: &str,
+    fields: &[(&String, &Field)],
+) -> TokenStream {
+    let struct_name_ident = format_ident!("{}", capitalize(element_name));
+    let trait_ident = format_ident!("{}Ext", capitalize(element_name));
+    let doc = format!(" Extension attributes for [`{}`]", capitalize(element_name));
+
+    let signatures = fields
+        .iter()
+        .map(|(field_name, field)| {
+            let method_ident = format_ident!("{}", camel_to_snake(field_name));
+            let field_type_tokens: TokenStream = field
+                .field_type
+                .parse()
+                .expect("Failed to parse field type");
+            let cfg = field.cfg_tokens();
+            let deprecated = field.deprecated_tokens();
+            quote! {
+                #cfg
+                #deprecated
+                fn #method_ident<T>(self, value: T) -> Self
+                where
+                    T: Into<#field_type_tokens>
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let bodies = fields.iter().map(|(field_name, field)| {
+        let attribute_name = field.attribute_name(field_name);
+        let field_type_tokens: TokenStream = field
+            .field_type
+            .parse()
+            .expect("Failed to parse field type");
+        quote! {
+            {
+                let value: #field_type_tokens = value.into();
+                self.attr(#attribute_name, value)
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #doc]
+        pub trait #trait_ident: Sized {
+            #( #signatures; )*
+        }
+
+        impl #trait_ident for #struct_name_ident {
+            #( #signatures #bodies )*
+        }
+    }
+}
+
+pub(crate) fn generate_shape_enum(config: &Config) -> TokenStream {
+    let enum_variants = config.elements.keys().map(|element_name| {
+        let struct_name_str = capitalize(element_name);
+        let struct_name_ident = format_ident!("{}", struct_name_str);
+        quote! {
+            #struct_name_ident(#struct_name_ident)
+        }
+    });
+
+    let variant_idents = config
+        .elements
+        .keys()
+        .map(|element_name| format_ident!("{}", capitalize(element_name)))
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        #[serde(tag = "type")]
+        pub enum Shape {
+            #( #enum_variants ),*,
+            String(String),
+            /// A node from outside the schema, see [`SvgNode`]
+            Custom(#[serde(with = "custom_node")] Box<dyn SvgNode>)
+        }
+
+        impl From<String> for Shape {
+            fn from(string: String) -> Self {
+                Self::String(string)
+            }
+        }
+
+
+        impl Shape {
+            pub fn custom<T: SvgNode + 'static>(node: T) -> Self {
+                Self::Custom(Box::new(node))
+            }
+
+            pub fn tag_name(&self) -> Option<&str> {
+                match self {
+                    #( Shape::#variant_idents(element) => Some(element.tag_name()), )*
+                    Shape::String(_) => None,
+                    Shape::Custom(node) => Some(node.tag_name()),
+                }
+            }
+
+            pub fn child_nodes(&self) -> &[Shape] {
+                match self {
+                    #( Shape::#variant_idents(element) => element.child_nodes(), )*
+                    Shape::String(_) => &[],
+                    Shape::Custom(node) => node.child_nodes(),
+                }
+            }
+
+            pub fn get_attribute(&self, name: &str) -> Option<String> {
+                match self {
+                    #( Shape::#variant_idents(element) => element.get_attribute(name), )*
+                    Shape::String(_) => None,
+                    Shape::Custom(node) => node.attributes().get(name).map(str::to_string),
+                }
+            }
+
+            pub fn write_svg(&self, svg: &mut String, options: &SerializeOptions) {
+                match self {
+                    #( Shape::#variant_idents(element) => element.write_svg(svg, options), )*
+                    Shape::String(string) => svg.push_str(string),
+                    Shape::Custom(node) => node.write_svg(svg, options),
+                }
+            }
+
+            pub fn to_svg_string(&self, options: &SerializeOptions) -> String {
+                let mut svg = String::new();
+                self.write_svg(&mut svg, options);
+                svg
+            }
+
+            /// Replaces every color in the tree with the result of `f`, see [`crate::recolor`]
+            /// for built-in transforms
+            pub fn map_colors(&mut self, f: &mut dyn FnMut(&Color) -> Color) {
+                match self {
+                    #( Shape::#variant_idents(element) => element.map_colors(f), )*
+                    Shape::String(_) => {}
+                    Shape::Custom(node) => node.map_colors(f),
+                }
+            }
+        }
+
+        impl std::fmt::Display for Shape {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.to_svg_string(&SerializeOptions::default()))
+            }
+        }
+    }
+}
+fn generate_struct(name: &str, element: &Element) -> TokenStream {
+    let struct_name = capitalize(name);
+    let mut fields = Vec::new();
+
+    for (field_name, field) in &element.fields {
+        let field_name_ident = format_ident!("{}", camel_to_snake(field_name));
+        let field_type_tokens: TokenStream = field
+            .field_type
+            .parse()
+            .expect("Failed to parse field type");
+        let cfg = field.cfg_tokens();
+        if element.is_required(field_name) {
+            fields.push(quote! {
+                pub #field_name_ident: #field_type_tokens
+            });
+            continue;
+        }
+        fields.push(quote! {
+            #cfg
+            pub #field_name_ident: Option<#field_type_tokens>
+        });
+    }
+
+    let struct_name_ident = format_ident!("{}", struct_name);
+
+    quote! {
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+     pub struct #struct_name_ident {
+           #( #fields ),*,
+            children: Vec<Shape>,
+            #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+            extra_attributes: Attributes
+         }
+     }
+}
+
+fn generate_to_string(name: &str, element: &Element, config: &Config) -> TokenStream {
+    let struct_name = capitalize(name);
+    let struct_name_ident = format_ident!("{}", struct_name);
+
+    let field_handling = element.fields.iter().map(|(field_name, field)| {
+        let field_name_ident = format_ident!("{}", camel_to_snake(field_name));
+        let attribute_name = field.attribute_name(field_name);
+        let written = |value: TokenStream| {
+            if config.option_aware_types.contains(&field.field_type) {
+                quote! { escape_attribute(&#value.to_svg_string(options)) }
+            } else {
+                quote! { escape_attribute(&#value.to_string()) }
+            }
+        };
+        if element.is_required(field_name) {
+            let value = written(quote! { self.#field_name_ident });
+            return quote! {
+                svg.push_str(&format!(" {}=\"{}\"", #attribute_name, #value));
+            };
+        }
+        let value = written(quote! { #field_name_ident });
+
+        let cfg = field.cfg_tokens();
+        // an inherited property equal to its default still overrides the value of the parent
+        let is_kept = match field.default_tokens().filter(|_| !field.is_inherited()) {
+            Some(default) => quote! {
+                && !(options.omits_defaults() && *#field_name_ident == #default)
+            },
+            None => quote! {},
+        };
+        quote! {
+            #cfg
+            if let Some(#field_name_ident) = &self.#field_name_ident #is_kept {
+                svg.push_str(&format!(" {}=\"{}\"", #attribute_name, #value));
+            }
+        }
+    });
+
+    quote! {
+        impl #struct_name_ident {
+            pub fn write_svg(&self, svg: &mut String, options: &SerializeOptions) {
+                svg.push_str(&format!("<{}", #name));
+
+                #( #field_handling )*
+                svg.push_str(&self.extra_attributes.to_string());
+
+                if self.children.is_empty() {
+                    svg.push_str("/>");
+                    return;
+                }
+
+                svg.push('>');
+                for child in self.children.iter() {
+                    child.write_svg(svg, options);
+                }
+                svg.push_str(&format!("</{}>", #name));
+            }
+
+            pub fn to_svg_string(&self, options: &SerializeOptions) -> String {
+                let mut svg = String::new();
+                self.write_svg(&mut svg, options);
+                svg
+            }
+        }
+
+        impl std::fmt::Display for #struct_name_ident {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}", self.to_svg_string(&SerializeOptions::default()))
+            }
+        }
+    }
+}
+fn generate_impl(name: &str, element: &Element, config: &Config) -> TokenStream {
+    let struct_name = capitalize(name);
+    let constructor_tokens = generate_constructor(element);
+
+    let builder_methods = element
+        .fields
+        .iter()
+        .map(|(field_name, field)| {
+            generate_builder_method(field_name, field, element.is_required(field_name))
+        })
+        .collect::<Vec<_>>();
+    let struct_name_ident = format_ident!("{}", struct_name);
+
+    let element_type_impls = element.element_types.iter().map(|element_type_str| {
+        let element_type_ident = format_ident!("{}", element_type_str);
+        quote! {
+            impl #element_type_ident for #struct_name_ident {}
+        }
+    });
+    let children_methods = generate_children_methods(element, config);
+    let node_methods = generate_node_methods(name, element);
+    let map_colors = generate_map_colors(element, config);
+    let effective_getters = element
+        .fields
+        .iter()
+        .filter(|(field_name, _)| !element.is_required(field_name))
+        .filter_map(|(field_name, field)| generate_effective_getter(field_name, field));
+
+    let default_impl = if element.required.is_empty() {
+        quote! {
+            impl Default for #struct_name_ident {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        #( #element_type_impls )*
+        #default_impl
+
+        impl<C: ValidChildOf<#struct_name_ident>> Extend<C> for #struct_name_ident {
+            fn extend<I: IntoIterator<Item = C>>(&mut self, children: I) {
+                self.children.extend(children.into_iter().map(Into::into));
+            }
+        }
+        impl #struct_name_ident {
+            #constructor_tokens
+            #( #builder_methods )*
+          #( #children_methods )*
+            #( #effective_getters )*
+            #node_methods
+            #map_colors
+        }
+    }
+}
+
+/// `map_colors`, going through the fields of one of the schema's `color_types`, CSS fields and
+/// untyped attributes before the children, whose text is CSS for `is_stylesheet` elements
+fn generate_map_colors(element: &Element, config: &Config) -> TokenStream {
+    let field_handling = element.fields.iter().filter_map(|(field_name, field)| {
+        let field_name_ident = format_ident!("{}", camel_to_snake(field_name));
+        let mapped = if config.color_types.contains(&field.field_type) {
+            quote! { value.map_colors(f) }
+        } else if field.is_css() {
+            quote! { map_css_colors(value, f) }
+        } else {
+            return None;
+        };
+        let cfg = field.cfg_tokens();
+        if element.is_required(field_name) {
+            return Some(quote! {
+                let value = &mut self.#field_name_ident;
+                #mapped;
+            });
+        }
+        Some(quote! {
+            #cfg
+            if let Some(value) = &mut self.#field_name_ident {
+                #mapped;
+            }
+        })
+    });
+
+    let child_handling = if element.is_stylesheet {
+        quote! {
+            match child {
+                Shape::String(css) => map_css_colors(css, f),
+                child => child.map_colors(f),
+            }
+        }
+    } else {
+        quote! { child.map_colors(f); }
+    };
+
+    quote! {
+        /// Replaces every color of the element and its descendants with the result of `f`,
+        /// see [`crate::recolor`] for built-in transforms
+        pub fn map_colors(&mut self, f: &mut dyn FnMut(&Color) -> Color) {
+            #( #field_handling )*
+            self.extra_attributes.map_colors(f);
+            for child in self.children.iter_mut() {
+                #child_handling
+            }
+        }
+    }
+}
+
+fn generate_effective_getter(field_name: &str, field: &Field) -> Option<TokenStream> {
+    let default = field.default_tokens()?;
+    let field_name_ident = format_ident!("{}", camel_to_snake(field_name));
+    let getter_ident = format_ident!("effective_{}", camel_to_snake(field_name));
+    let field_type_tokens: TokenStream = field
+        .field_type
+        .parse()
+        .expect("Failed to parse field type");
+    let cfg = field.cfg_tokens();
+    if field.is_inherited() {
+        let doc = format!(
+            " The `{}` value, or when unset the `inherited` value of the parent, or its spec \
+             default `{}` on the root",
+            field.attribute_name(field_name),
+            field.default.as_deref().unwrap_or_default()
+        );
+        return Some(quote! {
+            #cfg
+            #[doc = #doc]
+            pub fn #getter_ident(&self, inherited: Option<#field_type_tokens>) -> #field_type_tokens {
+                match (&self.#field_name_ident, inherited) {
+                    (Some(value), _) => Clone::clone(value),
+                    (None, Some(inherited)) => inherited,
+                    (None, None) => #default,
+                }
+            }
+        });
+    }
+    let doc = format!(
+        " The `{}` value, or its spec default `{}` when unset",
+        field.attribute_name(field_name),
+        field.default.as_deref().unwrap_or_default()
+    );
+    Some(quote! {
+        #cfg
+        #[doc = #doc]
+        pub fn #getter_ident(&self) -> #field_type_tokens {
+            match &self.#field_name_ident {
+                Some(value) => Clone::clone(value),
+                None => #default,
+            }
+        }
+    })
+}
+
+fn generate_node_methods(name: &str, element: &Element) -> TokenStream {
+    let attribute_arms = element.fields.iter().map(|(field_name, field)| {
+        let field_name_ident = format_ident!("{}", camel_to_snake(field_name));
+        let attribute_name = field.attribute_name(field_name);
+        let cfg = field.cfg_tokens();
+        if element.is_required(field_name) {
+            return quote! {
+                #attribute_name => Some(self.#field_name_ident.to_string())
+            };
+        }
+        quote! {
+            #cfg
+            #attribute_name => self.#field_name_ident.as_ref().map(|value| value.to_string())
+        }
+    });
+
+    let stable_attribute_names = element
+        .fields
+        .iter()
+        .filter(|(_, field)| !field.is_experimental.unwrap_or(false))
+        .map(|(field_name, field)| field.attribute_name(field_name));
+    let all_attribute_names = element
+        .fields
+        .iter()
+        .map(|(field_name, field)| field.attribute_name(field_name));
+
+    quote! {
+        /// Names of the typed attributes, which can't be set through [`Self::attr`]
+        #[cfg(not(feature = "experimental"))]
+        pub const ATTRIBUTES: &'static [&'static str] = &[ #( #stable_attribute_names ),* ];
+        /// Names of the typed attributes, which can't be set through [`Self::attr`]
+        #[cfg(feature = "experimental")]
+        pub const ATTRIBUTES: &'static [&'static str] = &[ #( #all_attribute_names ),* ];
+
+        pub fn tag_name(&self) -> &'static str {
+            #name
+        }
+
+        pub fn child_nodes(&self) -> &[Shape] {
+            &self.children
+        }
+
+        pub fn get_attribute(&self, name: &str) -> Option<String> {
+            match name {
+                #( #attribute_arms, )*
+                _ => self.extra_attributes.get(name).map(str::to_string),
+            }
+        }
+
+        pub fn extra_attributes(&self) -> &Attributes {
+            &self.extra_attributes
+        }
+
+        /// Sets an attribute that has no typed builder, such as a vendor extension.
+        ///
+        /// # Panics
+        /// If `name` is not a valid attribute name or is one of [`Self::ATTRIBUTES`]
+        pub fn attr<N: Into<String>, V: ToString>(self, name: N, value: V) -> Self {
+            self.try_attr(name, value)
+                .unwrap_or_else(|error| panic!("{}", error))
+        }
+
+        pub fn try_attr<N: Into<String>, V: ToString>(mut self, name: N, value: V) -> Result<Self, AttributeError> {
+            self.extra_attributes
+                .try_insert(name.into(), value.to_string(), Self::ATTRIBUTES)?;
+            Ok(self)
+        }
+
+        /// Sets a `data-*` attribute, `key` is the part after `data-`.
+        ///
+        /// # Panics
+        /// If `key` contains uppercase letters or is not a valid attribute name
+        pub fn data<V: ToString>(self, key: &str, value: V) -> Self {
+            self.try_data(key, value)
+                .unwrap_or_else(|error| panic!("{}", error))
+        }
+
+        pub fn try_data<V: ToString>(self, key: &str, value: V) -> Result<Self, AttributeError> {
+            self.try_attr(Attributes::data_name(key)?, value)
+        }
+    }
+}
+
+/// The child types of an element with categories expanded into the element structs that
+/// belong to them, along with any external types
+pub(crate) fn allowed_children(element: &Element, config: &Config) -> BTreeSet<String> {
+    let mut children = BTreeSet::new();
+    for child_type in &element.valid_child_types {
+        if config.element_types.contains_key(child_type) {
+            children.extend(
+                config
+                    .elements
+                    .iter()
+                    .filter(|(_, child)| child.element_types.contains(child_type))
+                    .map(|(child_name, _)| capitalize(child_name)),
+            );
+        } else {
+            children.insert(child_type.clone());
+        }
+    }
+    children
+}
+
+pub(crate) fn generate_valid_child_trait() -> TokenStream {
+    quote! {
+        /// Implemented for every type that `P` accepts as a child, per its `valid_child_types`
+        #[diagnostic::on_unimplemented(
+            message = "`{Self}` is not a valid child of `{P}`",
+            label = "`{P}` does not accept this child",
+            note = "the elements `{P}` accepts are listed in `{P}::ALLOWED_CHILDREN`"
+        )]
+        pub trait ValidChildOf<P>: Into<Shape> {}
+    }
+}
+
+/// `ValidChildOf<Parent>` for each allowed child, limited to the children `include` accepts
+pub(crate) fn generate_valid_child_impls(
+    parent_name: &str,
+    element: &Element,
+    config: &Config,
+    include: impl Fn(&str) -> bool,
+) -> TokenStream {
+    let parent_ident = format_ident!("{}", capitalize(parent_name));
+    let child_types = allowed_children(element, config)
+        .into_iter()
+        .filter(|child| include(child))
+        .map(|child| {
+            child
+                .parse::<TokenStream>()
+                .expect("Failed to parse child type")
+        });
+
+    quote! {
+        #( impl ValidChildOf<#parent_ident> for #child_types {} )*
+    }
+}
+
+fn generate_children_methods(element: &Element, config: &Config) -> Vec<TokenStream> {
+    let allowed_tag_names = config
+        .elements
+        .keys()
+        .filter(|child_name| allowed_children(element, config).contains(&capitalize(child_name)));
+    let doc = format!(
+        " Adds a child allowed by `{}`, see [`Self::ALLOWED_CHILDREN`]",
+        element.valid_child_types.join("`, `")
+    );
+    let mut methods = vec![quote! {
+        /// Tag names of the elements this element accepts as children
+        pub const ALLOWED_CHILDREN: &'static [&'static str] = &[ #( #allowed_tag_names ),* ];
+
+        #[doc = #doc]
+        pub fn add_child<C: ValidChildOf<Self>>(mut self, child: C) -> Self {
+            self.children.push(child.into());
+            self
+        }
+
+        pub fn add_children<I>(mut self, children: I) -> Self
+        where
+            I: IntoIterator,
+            I::Item: ValidChildOf<Self>,
+        {
+            self.children.extend(children.into_iter().map(Into::into));
+            self
+        }
+
+        pub fn add_child_if<C: ValidChildOf<Self>>(self, condition: bool, child: C) -> Self {
+            if condition {
+                self.add_child(child)
+            } else {
+                self
+            }
+        }
+
+        pub fn add_child_opt<C: ValidChildOf<Self>>(self, child: Option<C>) -> Self {
+            match child {
+                Some(child) => self.add_child(child),
+                None => self,
+            }
+        }
+
+        /// Adds the children pushed by `build`, for loops and conditionals in a builder chain
+        pub fn children<F: FnOnce(&mut Children<Self>)>(mut self, build: F) -> Self {
+            let mut children = Children::new();
+            build(&mut children);
+            self.children.extend(children.into_shapes());
+            self
+        }
+    }];
+    for child_type in element.valid_child_types.iter() {
+        let method_name_ident = format_ident!("add_child_{}", camel_to_snake(child_type));
+
+        if config.element_types.contains_key(child_type) {
+            let child_type_tokens: TokenStream = child_type.to_string().parse().unwrap();
+            methods.push(quote! {
+                pub fn #method_name_ident <T>(mut self, child: T) -> Self
+                where
+                    T: Into<Shape> + #child_type_tokens,
+                {
+                    self.children.push(child.into());
+                    self
+                }
+            });
+        } else {
+            let child_struct_name = format_ident!("{}", capitalize(child_type));
+            methods.push(quote! {
+                pub fn #method_name_ident(mut self, child: #child_struct_name) -> Self
+                {
+                    self.children.push(child.into());
+                    self
+                }
+            });
+        }
+    }
+    methods
+}
+
+fn generate_constructor(element: &Element) -> TokenStream {
+    let required_params = element.required.iter().map(|field_name| {
+        let field_name_ident = format_ident!("{}", camel_to_snake(field_name));
+        let field_type_tokens: TokenStream = element.fields[field_name]
+            .field_type
+            .parse()
+            .expect("Failed to parse field type");
+        quote! {
+            #field_name_ident: impl Into<#field_type_tokens>
+        }
+    });
+
+    let field_assignments = element.fields.iter().map(|(field_name, field)| {
+        let field_name_ident = format_ident!("{}", camel_to_snake(field_name));
+        if element.is_required(field_name) {
+            return quote! {
+                #field_name_ident: #field_name_ident.into()
+            };
+        }
+
+        let cfg = field.cfg_tokens();
+        quote! {
+            #cfg
+            #field_name_ident: None
+        }
+    });
+
+    quote! {
+        pub fn new(#( #required_params ),*) -> Self {
+            Self {
+                #( #field_assignments ),*,
+                 children: Vec::new(),
+                 extra_attributes: Attributes::new()
+            }
+        }
+    }
+}
+fn generate_builder_method(field_name: &str, field: &Field, is_required: bool) -> TokenStream {
+    let field_name_ident = format_ident!("{}", camel_to_snake(field_name));
+    let param_type_tokens: TokenStream = field
+        .field_type
+        .parse()
+        .expect("Failed to parse field type");
+    let cfg = field.cfg_tokens();
+    let deprecated = field.deprecated_tokens();
+    let value = if is_required {
+        quote! { value.into() }
+    } else {
+        quote! { Some(value.into()) }
+    };
+    quote! {
+        #cfg
+        #deprecated
+        pub fn #field_name_ident<T>(mut self, value: T) -> Self
+        where
+        T: Into<#param_type_tokens>
+        {
+            self.#field_name_ident = #value;
+            self
+        }
+    }
+}
+
+pub(crate) fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+    }
+}
+
+pub(crate) fn format_rust_code(code: &str) -> String {
+    match syn::parse_file(code) {
+        Ok(syntax_tree) => prettyplease::unparse(&syntax_tree),
+        Err(e) => {
+            println!("cargo:warning=Failed to parse generated code: {}", e);
+            println!("cargo:warning=Using unformatted code");
+            code.to_string()
+        }
+    }
+}
+
+pub(crate) fn camel_to_snake(s: &str) -> String {
+    let mut result = String::new();
+    let mut prev_was_lower = false;
+
+    for ch in s.replace("-", "_").chars() {
+        if ch.is_uppercase() {
+            if prev_was_lower {
+                result.push('_');
+            }
+            result.extend(ch.to_lowercase());
+            prev_was_lower = false;
+        } else {
+            result.push(ch);
+            prev_was_lower = ch.is_lowercase();
+        }
+    }
+
+    result
+}
@@ -0,0 +1,553 @@
+//! The code generator behind [easy-svg](https://crates.io/crates/easy-svg).
+//!
+//! easy-svg's elements are described in a YAML schema, [`BUILTIN_SCHEMA`], which this crate
+//! validates and turns into Rust: a struct per element with builders, serialization and a
+//! conversion into `Shape`, a trait per category and a keyword enum per entry of `enums`.
+//!
+//! Crates with their own SVG extensions can run the same generator from their `build.rs`
+//! against an extension schema. Extension elements may derive the built-in DOM interfaces,
+//! implement the built-in categories so they can be added as children of built-in elements,
+//! and accept built-in elements as children. They implement `SvgNode` and are stored in
+//! `Shape::Custom`. Attributes an extension adds to built-in elements become a
+//! `<Element>Ext` trait of typed builders.
+//!
+//! ```no_run
+//! // build.rs
+//! let generated = easy_svg_codegen::Generator::new()
+//!     .extension_file("my_elements.yml")
+//!     .expect("Failed to read my_elements.yml")
+//!     .generate()
+//!     .unwrap_or_else(|error| panic!("{}", error));
+//! generated
+//!     .write_to(std::env::var("OUT_DIR").unwrap())
+//!     .expect("Failed to write generated code");
+//! ```
+//!
+//! The generated code is then included with
+//! `include!(concat!(env!("OUT_DIR"), "/generated.rs"));`, which defines `pub mod elements`
+//! and needs `serde` with the `derive` feature as a dependency. Fields marked `is_experimental`,
+//! including those derived from the built-in interfaces, are gated behind the crate's own
+//! `experimental` feature, which should enable `easy-svg/experimental`.
+//!
+//! # Schema
+//!
+//! Every top level section is optional in an extension schema.
+//!
+//! - `element_types`: categories such as `ShapeElement`, each generating a marker trait
+//! - `derives`: DOM interfaces with `fields` and the `derives` they inherit from
+//! - `attributes`: fields shared by the listed `elements`
+//! - `elements`: keyed by tag name, with `element_types`, `derives`, `valid_child_types`
+//!   (categories, capitalized element names or `external_child_types`), `fields` and
+//!   optionally `required`, the fields `new` takes as arguments in that order, and
+//!   `is_stylesheet`, for elements whose text children are CSS that `map_colors` recolors
+//! - `external_types`: hand-written field types, which a field `type` must name unless it is
+//!   an enum, a Rust primitive or one of the other type lists
+//! - `external_child_types`: non-element types implementing `Into<Shape>`
+//! - `option_aware_types`: field types written with a `to_svg_string(&SerializeOptions)`
+//!   method instead of `Display`
+//! - `color_types`: field types with a `map_colors(&mut dyn FnMut(&Color) -> Color)` method,
+//!   which the `map_colors` of elements calls
+//! - `enums`: keyword types with a `description`, `default` keyword and `variants`
+//!
+//! A field has a `type`, and optionally `attribute` (the markup name when the key is a DOM
+//! property), `default` (a Rust expression for the spec initial value), `is_inherited` (an
+//! inherited CSS property, which is never omitted as a default and whose `effective_` getter
+//! takes the value of the parent), `is_deprecated`, `deprecated_note`, `is_experimental`
+//! (generated behind an `experimental` cargo feature), `idl_only` (skipped, as it has no content
+//! attribute) and `is_css` (a `String` of CSS declarations whose colors `map_colors` replaces).
+
+mod generate;
+mod schema;
+mod validate;
+
+use quote::quote;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+use generate::{
+    allowed_children, camel_to_snake, capitalize, format_rust_code, generate_attribute_extension,
+    generate_category_traits, generate_element, generate_enums, generate_shape_enum,
+    generate_valid_child_impls, generate_valid_child_trait,
+};
+use schema::{Config, Element, Field};
+use validate::{SourceMap, validate_config};
+
+/// The schema of the elements that ship with easy-svg
+pub const BUILTIN_SCHEMA: &str = include_str!("../svg_elements.yml");
+
+const BUILTIN_SCHEMA_NAME: &str = "svg_elements.yml";
+
+/// Generates Rust code from the built-in schema and any extension schemas
+#[derive(Debug, Clone)]
+pub struct Generator {
+    crate_path: String,
+    extensions: Vec<(String, String)>,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Generator {
+    pub fn new() -> Self {
+        Self {
+            crate_path: "::easy_svg".to_string(),
+            extensions: Vec::new(),
+        }
+    }
+
+    /// The path generated code uses to refer to easy-svg, `::easy_svg` by default
+    pub fn crate_path<P: Into<String>>(mut self, crate_path: P) -> Self {
+        self.crate_path = crate_path.into();
+        self
+    }
+
+    /// Adds an extension schema, `name` is used when reporting problems in it
+    pub fn extension<N: Into<String>, Y: Into<String>>(mut self, name: N, yaml: Y) -> Self {
+        self.extensions.push((name.into(), yaml.into()));
+        self
+    }
+
+    /// Reads an extension schema and asks cargo to rerun the build script when it changes
+    pub fn extension_file<P: AsRef<Path>>(self, path: P) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let yaml = std::fs::read_to_string(path)?;
+        println!("cargo:rerun-if-changed={}", path.display());
+        Ok(self.extension(path.display().to_string(), yaml))
+    }
+
+    /// Validates the schemas and generates the code.
+    ///
+    /// Without extensions this is easy-svg's own code, otherwise only the extension's items
+    /// are generated and the built-in ones are referred to through [`Self::crate_path`]
+    pub fn generate(&self) -> Result<Generated, SchemaError> {
+        let mut source = SourceMap::default();
+        source.add(BUILTIN_SCHEMA_NAME, BUILTIN_SCHEMA);
+        for (name, yaml) in &self.extensions {
+            source.add(name, yaml);
+        }
+
+        let mut config = parse(BUILTIN_SCHEMA_NAME, BUILTIN_SCHEMA)?;
+        let mut owned = Owned::default();
+        let mut problems = Vec::new();
+        for (name, yaml) in &self.extensions {
+            let extension = parse(name, yaml)?;
+            owned.record(&extension);
+            for (section, key) in config.merge(extension) {
+                problems.push(source.describe(
+                    source.entry(section, &key),
+                    &format!("`{}` is already defined in `{}`", key, section),
+                ));
+            }
+        }
+
+        schema::remove_idl_only_fields(&mut config);
+        problems.extend(validate_config(&config, &source));
+        if !problems.is_empty() {
+            return Err(SchemaError { problems });
+        }
+        config.resolve();
+
+        let krate: proc_macro2::TokenStream = self.crate_path.parse().map_err(|_| SchemaError {
+            problems: vec![format!("invalid crate path `{}`", self.crate_path)],
+        })?;
+
+        if self.extensions.is_empty() {
+            Ok(generate_builtin(&config, &krate))
+        } else {
+            Ok(generate_extension(&config, &owned, &krate))
+        }
+    }
+}
+
+/// The resolved built-in schema, for tools that need to know which elements and attributes
+/// exist, such as the `svg!` macro
+#[derive(Debug)]
+pub struct Schema {
+    config: Config,
+}
+
+impl Schema {
+    pub fn builtin() -> Self {
+        let mut config =
+            parse(BUILTIN_SCHEMA_NAME, BUILTIN_SCHEMA).expect("the built-in schema is valid");
+        schema::remove_idl_only_fields(&mut config);
+        config.resolve();
+        Self { config }
+    }
+
+    pub fn element(&self, tag_name: &str) -> Option<ElementSchema<'_>> {
+        self.config
+            .elements
+            .get_key_value(tag_name)
+            .map(|(tag_name, element)| ElementSchema {
+                tag_name,
+                element,
+                config: &self.config,
+            })
+    }
+
+    pub fn tag_names(&self) -> impl Iterator<Item = &str> {
+        self.config.elements.keys().map(String::as_str)
+    }
+}
+
+/// An element of a [`Schema`], with its fields and derived attributes merged in
+#[derive(Debug, Clone, Copy)]
+pub struct ElementSchema<'a> {
+    tag_name: &'a str,
+    element: &'a Element,
+    config: &'a Config,
+}
+
+impl<'a> ElementSchema<'a> {
+    pub fn tag_name(&self) -> &'a str {
+        self.tag_name
+    }
+
+    pub fn struct_name(&self) -> String {
+        capitalize(self.tag_name)
+    }
+
+    /// Attribute names as written in markup
+    pub fn attribute_names(&self) -> impl Iterator<Item = &'a str> {
+        self.element
+            .fields
+            .iter()
+            .map(|(field_name, field)| field.attribute_name(field_name))
+    }
+
+    /// Attribute names that `new` takes, in argument order
+    pub fn required_attributes(&self) -> impl Iterator<Item = &'a str> {
+        let element = self.element;
+        element
+            .required
+            .iter()
+            .map(|field_name| element.fields[field_name].attribute_name(field_name))
+    }
+
+    /// The builder method setting the attribute written as `attribute_name` in markup
+    pub fn builder(&self, attribute_name: &str) -> Option<String> {
+        self.element
+            .fields
+            .iter()
+            .find(|(field_name, field)| field.attribute_name(field_name) == attribute_name)
+            .map(|(field_name, _)| camel_to_snake(field_name))
+    }
+
+    /// Whether the attribute is only generated with the `experimental` feature
+    pub fn is_experimental(&self, attribute_name: &str) -> bool {
+        self.element.fields.iter().any(|(field_name, field)| {
+            field.attribute_name(field_name) == attribute_name && field.is_experimental()
+        })
+    }
+
+    pub fn allows_child(&self, tag_name: &str) -> bool {
+        allowed_children(self.element, self.config).contains(&capitalize(tag_name))
+    }
+
+    pub fn allows_text(&self) -> bool {
+        allowed_children(self.element, self.config).contains("String")
+    }
+}
+
+/// Generated source files, keyed by file name
+#[derive(Debug, Clone)]
+pub struct Generated {
+    files: Vec<(String, String)>,
+}
+
+impl Generated {
+    pub fn files(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.files
+            .iter()
+            .map(|(name, code)| (name.as_str(), code.as_str()))
+    }
+
+    /// Writes every file into `directory`, usually `OUT_DIR`
+    pub fn write_to<P: AsRef<Path>>(&self, directory: P) -> std::io::Result<()> {
+        for (name, code) in &self.files {
+            std::fs::write(directory.as_ref().join(name), code)?;
+        }
+        Ok(())
+    }
+}
+
+/// Problems found in the schemas, each prefixed with the file and line it was found at
+#[derive(Debug, Clone)]
+pub struct SchemaError {
+    problems: Vec<String>,
+}
+
+impl SchemaError {
+    pub fn problems(&self) -> &[String] {
+        &self.problems
+    }
+}
+
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the schema has {} problem(s):\n{}",
+            self.problems.len(),
+            self.problems.join("\n")
+        )
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+fn parse(name: &str, yaml: &str) -> Result<Config, SchemaError> {
+    serde_yaml::from_str(yaml).map_err(|error| SchemaError {
+        problems: vec![format!("{}: {}", name, error)],
+    })
+}
+
+/// Names defined by extension schemas, which are the only ones generated in extension mode
+#[derive(Default)]
+struct Owned {
+    elements: Vec<String>,
+    element_types: Vec<String>,
+    attributes: Vec<String>,
+    enums: Vec<String>,
+}
+
+impl Owned {
+    fn record(&mut self, extension: &Config) {
+        self.elements.extend(extension.elements.keys().cloned());
+        self.element_types
+            .extend(extension.element_types.keys().cloned());
+        self.attributes.extend(extension.attributes.keys().cloned());
+        self.enums.extend(extension.enums.keys().cloned());
+    }
+}
+
+fn generate_builtin(config: &Config, krate: &proc_macro2::TokenStream) -> Generated {
+    let category_traits = generate_category_traits(config.element_types.keys());
+    let valid_child_trait = generate_valid_child_trait();
+    let element_code = config
+        .elements
+        .iter()
+        .map(|(element_name, element)| generate_element(element_name, element, config, true));
+    let valid_child_impls = config.elements.iter().map(|(element_name, element)| {
+        generate_valid_child_impls(element_name, element, config, |_| true)
+    });
+    let enums = generate_enums(config, config.enums.keys());
+    let shape_enum = generate_shape_enum(config);
+
+    let elements = quote! {
+        pub mod elements{
+            use #krate::types::*;
+            use serde::{Deserialize, Serialize};
+            use #krate::Shape;
+            use #krate::children::Children;
+            use #krate::types::unions::*;
+            use #krate::serialize::SerializeOptions;
+            use #krate::recolor::map_css_colors;
+
+            #category_traits
+            #valid_child_trait
+            #( #element_code )*
+            #( #valid_child_impls )*
+        }
+    };
+    let enums = quote! {
+        use serde::{Deserialize, Serialize};
+        use #krate::types::ParseKeywordError;
+
+        #enums
+    };
+    let shape = quote! {
+        use serde::{Deserialize, Serialize};
+        use #krate::elements::*;
+        use #krate::node::{custom_node, SvgNode};
+        use #krate::serialize::SerializeOptions;
+        use #krate::types::Color;
+        #shape_enum
+    };
+
+    Generated {
+        files: vec![
+            (
+                "generated.rs".to_string(),
+                format_rust_code(&elements.to_string()),
+            ),
+            ("enums.rs".to_string(), format_rust_code(&enums.to_string())),
+            ("shape.rs".to_string(), format_rust_code(&shape.to_string())),
+        ],
+    }
+}
+
+fn generate_extension(
+    config: &Config,
+    owned: &Owned,
+    krate: &proc_macro2::TokenStream,
+) -> Generated {
+    let category_traits = generate_category_traits(owned.element_types.iter());
+    let enums = generate_enums(config, owned.enums.iter());
+    let element_code = owned.elements.iter().map(|element_name| {
+        generate_element(element_name, &config.elements[element_name], config, false)
+    });
+
+    let mut builtin_attributes: BTreeMap<&String, Vec<(&String, &Field)>> = BTreeMap::new();
+    for attribute_name in &owned.attributes {
+        let attribute = &config.attributes[attribute_name];
+        for element_name in &attribute.elements {
+            if !owned.elements.contains(element_name) {
+                builtin_attributes
+                    .entry(element_name)
+                    .or_default()
+                    .push((attribute_name, &attribute.field));
+            }
+        }
+    }
+    let owned_structs = owned
+        .elements
+        .iter()
+        .map(|element_name| capitalize(element_name))
+        .collect::<Vec<_>>();
+    let valid_child_impls = config.elements.iter().map(|(element_name, element)| {
+        let is_owned = owned.elements.contains(element_name);
+        generate_valid_child_impls(element_name, element, config, |child| {
+            is_owned
+                || owned_structs
+                    .iter()
+                    .any(|owned_struct| owned_struct == child)
+        })
+    });
+    let attribute_extensions = builtin_attributes
+        .iter()
+        .map(|(element_name, fields)| generate_attribute_extension(element_name, fields));
+
+    let elements = quote! {
+        pub mod elements {
+            #![allow(unused_imports)]
+            use serde::{Deserialize, Serialize};
+            use #krate::children::Children;
+            use #krate::elements::*;
+            use #krate::node::SvgNode;
+            use #krate::recolor::map_css_colors;
+            use #krate::serialize::SerializeOptions;
+            use #krate::types::unions::*;
+            use #krate::types::*;
+            use #krate::Shape;
+
+            #enums
+            #category_traits
+            #( #element_code )*
+            #( #valid_child_impls )*
+            #( #attribute_extensions )*
+        }
+    };
+
+    Generated {
+        files: vec![(
+            "generated.rs".to_string(),
+            format_rust_code(&elements.to_string()),
+        )],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Generator;
+
+    #[test]
+    fn test_extension_schema() {
+        let generated = Generator::new()
+            .extension(
+                "gauge.yml",
+                r#"
+attributes:
+  data-kind:
+    type: "String"
+    elements: [ "rect" ]
+elements:
+  gauge:
+    element_types: [ "ShapeElement" ]
+    derives: [ "SVGGeometryElement" ]
+    valid_child_types: [ "Rect" ]
+    fields:
+      value: { type: "f64" }
+"#,
+            )
+            .generate()
+            .unwrap();
+        let (name, code) = generated.files().next().unwrap();
+        assert_eq!(name, "generated.rs");
+        assert!(code.contains("pub struct Gauge"));
+        assert!(code.contains("impl ShapeElement for Gauge"));
+        assert!(code.contains("impl SvgNode for Gauge"));
+        assert!(code.contains("pub trait RectExt"));
+        assert!(!code.contains("pub struct Rect"));
+
+        let error = Generator::new()
+            .extension(
+                "duplicate.yml",
+                "elements:\n  rect:\n    element_types: []\n    derives: []\n    valid_child_types: []\n    fields: {}\n",
+            )
+            .generate()
+            .unwrap_err();
+        assert_eq!(
+            error.problems(),
+            ["duplicate.yml:2: `rect` is already defined in `elements`"]
+        );
+
+        let error = Generator::new()
+            .extension(
+                "required.yml",
+                "elements:\n  dial:\n    element_types: []\n    derives: []\n    valid_child_types: []\n    required: [ \"vlaue\" ]\n    fields:\n      value: { type: \"f64\" }\n",
+            )
+            .generate()
+            .unwrap_err();
+        assert_eq!(
+            error.problems(),
+            [
+                "required.yml:6: element `dial` requires unknown field `vlaue`, did you mean `value`?"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schema_validation() {
+        let error = Generator::new()
+            .extension(
+                "typos.yml",
+                r#"
+external_types: [ "GaugeStyle" ]
+attributes:
+  data-kind:
+    type: "List<Sting>"
+    elements: [ "rect", "gague" ]
+elements:
+  gauge:
+    element_types: [ "ShapeElemnt" ]
+    derives: [ "SVGGeometryElement" ]
+    valid_child_types: [ "Rcet" ]
+    is_stylesheet: true
+    fields:
+      value: { type: "LenghtOrPercentage" }
+      style: { type: "GaugeStyle" }
+      unit: { type: "&str" }
+"#,
+            )
+            .generate()
+            .unwrap_err();
+        assert_eq!(
+            error.problems(),
+            [
+                "typos.yml:9: element `gauge` has unknown category `ShapeElemnt`, did you mean `ShapeElement`?",
+                "typos.yml:11: element `gauge` allows unknown child type `Rcet`, expected a category, a capitalized element name or one of `external_child_types`, did you mean `Rect`?",
+                "typos.yml:16: field `unit` of element `gauge` has invalid type `&str`: expected a type name such as `f64` or `List<String>`",
+                "typos.yml:14: field `value` of element `gauge` has invalid type `LenghtOrPercentage`: unknown type `LenghtOrPercentage`, expected an enum, a Rust primitive or one of `external_types`, did you mean `LengthOrPercentage`?",
+                "typos.yml:4: attribute `data-kind` has invalid type `List<Sting>`: unknown type `Sting`, expected an enum, a Rust primitive or one of `external_types`, did you mean `String`?",
+                "typos.yml:6: attribute `data-kind` targets unknown element `gague`, did you mean `gauge`?",
+                "typos.yml:8: element `gauge` sets `is_stylesheet` but does not take `String` children",
+            ]
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+
+use crate::generate::capitalize;
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct Config {
+    #[serde(default)]
+    pub(crate) elements: BTreeMap<String, Element>,
+    #[serde(default)]
+    pub(crate) derives: BTreeMap<String, Derivable>,
+    #[serde(default)]
+    pub(crate) element_types: BTreeMap<String, ElementType>,
+    #[serde(default)]
+    pub(crate) attributes: BTreeMap<String, Attribute>,
+    /// Hand-written types that fields may use, besides enums and Rust primitives
+    #[serde(default)]
+    pub(crate) external_types: Vec<String>,
+    /// Child types that are neither elements nor categories, but convert into `Shape`
+    #[serde(default)]
+    pub(crate) external_child_types: Vec<String>,
+    /// Field types written with `to_svg_string(options)` rather than `Display`
+    #[serde(default)]
+    pub(crate) option_aware_types: Vec<String>,
+    /// Field types with a `map_colors` method, which the `map_colors` of elements goes through
+    #[serde(default)]
+    pub(crate) color_types: Vec<String>,
+    /// Keyword types generated into `crate::types`
+    #[serde(default)]
+    pub(crate) enums: BTreeMap<String, KeywordEnum>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct KeywordEnum {
+    pub(crate) description: Option<String>,
+    /// Spelling of the default variant
+    pub(crate) default: Option<String>,
+    pub(crate) variants: Vec<KeywordVariant>,
+}
+
+/// A keyword as written in markup, optionally with an explicit variant name
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub(crate) enum KeywordVariant {
+    Keyword(String),
+    Named { name: String, keyword: String },
+}
+
+impl KeywordVariant {
+    pub(crate) fn keyword(&self) -> &str {
+        match self {
+            KeywordVariant::Keyword(keyword) | KeywordVariant::Named { keyword, .. } => keyword,
+        }
+    }
+
+    /// The variant name, `spacingAndGlyphs` and `no-referrer` become `SpacingAndGlyphs` and `NoReferrer`
+    pub(crate) fn name(&self) -> String {
+        match self {
+            KeywordVariant::Named { name, .. } => name.clone(),
+            KeywordVariant::Keyword(keyword) => {
+                keyword.split(['-', '_', ' ']).map(capitalize).collect()
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct Attribute {
+    pub(crate) elements: Vec<String>,
+    #[serde(flatten)]
+    pub(crate) field: Field,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub(crate) struct Derivable {
+    pub(crate) fields: BTreeMap<String, Field>,
+    pub(crate) derives: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub(crate) struct ElementType {}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct Element {
+    pub(crate) derives: Vec<String>,
+    pub(crate) fields: BTreeMap<String, Field>,
+    pub(crate) element_types: Vec<String>,
+    pub(crate) valid_child_types: Vec<String>,
+    /// Fields taken by `new`, in order, for attributes the element renders nothing without
+    #[serde(default)]
+    pub(crate) required: Vec<String>,
+    /// Text children are a stylesheet, as in `style`, whose colors `map_colors` replaces
+    #[serde(default)]
+    pub(crate) is_stylesheet: bool,
+}
+
+impl Element {
+    pub(crate) fn is_required(&self, field_name: &str) -> bool {
+        self.required.iter().any(|required| required == field_name)
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub(crate) struct Field {
+    #[serde(rename = "type")]
+    pub(crate) field_type: String,
+    pub(crate) is_deprecated: Option<bool>,
+    /// Shown in the deprecation warning, e.g. what to use instead
+    pub(crate) deprecated_note: Option<String>,
+    /// Experimental fields are only generated with the `experimental` cargo feature
+    pub(crate) is_experimental: Option<bool>,
+    /// The content attribute written to markup, when the key is a DOM property such as `className`
+    pub(crate) attribute: Option<String>,
+    /// DOM properties without a content attribute, e.g. `innerHtml`, which are not generated
+    pub(crate) idl_only: Option<bool>,
+    /// Rust expression for the value used when the attribute is absent, per the spec
+    pub(crate) default: Option<String>,
+    /// An inherited CSS property, whose absence means the value of the parent rather than the
+    /// default
+    pub(crate) is_inherited: Option<bool>,
+    /// A `String` of CSS declarations, such as `style`, whose colors `map_colors` replaces
+    pub(crate) is_css: Option<bool>,
+}
+
+impl Field {
+    pub(crate) fn attribute_name<'a>(&'a self, field_name: &'a str) -> &'a str {
+        self.attribute.as_deref().unwrap_or(field_name)
+    }
+
+    pub(crate) fn is_idl_only(&self) -> bool {
+        self.idl_only.unwrap_or(false)
+    }
+
+    pub(crate) fn is_inherited(&self) -> bool {
+        self.is_inherited.unwrap_or(false)
+    }
+
+    pub(crate) fn is_css(&self) -> bool {
+        self.is_css.unwrap_or(false)
+    }
+
+    pub(crate) fn default_tokens(&self) -> Option<TokenStream> {
+        self.default.as_ref().map(|default| {
+            default
+                .parse::<TokenStream>()
+                .expect("Failed to parse default")
+        })
+    }
+
+    pub(crate) fn is_experimental(&self) -> bool {
+        self.is_experimental.unwrap_or(false)
+    }
+
+    pub(crate) fn cfg_tokens(&self) -> TokenStream {
+        if self.is_experimental() {
+            quote! { #[cfg(feature = "experimental")] }
+        } else {
+            quote! {}
+        }
+    }
+
+    pub(crate) fn deprecated_tokens(&self) -> TokenStream {
+        if !self.is_deprecated.unwrap_or(false) {
+            return quote! {};
+        }
+        match &self.deprecated_note {
+            Some(note) => quote! { #[deprecated(note = #note)] },
+            None => quote! { #[deprecated] },
+        }
+    }
+}
+
+pub(crate) fn remove_idl_only_fields(config: &mut Config) {
+    for element in config.elements.values_mut() {
+        element.fields.retain(|_, field| !field.is_idl_only());
+    }
+    for derivable in config.derives.values_mut() {
+        derivable.fields.retain(|_, field| !field.is_idl_only());
+    }
+}
+
+impl Config {
+    /// Moves the entries of an extension schema into this one, returning the `(section, key)` of
+    /// every entry that is already defined and was left untouched
+    pub(crate) fn merge(&mut self, extension: Config) -> Vec<(&'static str, String)> {
+        let mut duplicates = Vec::new();
+        merge_section(
+            &mut self.elements,
+            extension.elements,
+            "elements",
+            &mut duplicates,
+        );
+        merge_section(
+            &mut self.derives,
+            extension.derives,
+            "derives",
+            &mut duplicates,
+        );
+        merge_section(
+            &mut self.element_types,
+            extension.element_types,
+            "element_types",
+            &mut duplicates,
+        );
+        merge_section(
+            &mut self.attributes,
+            extension.attributes,
+            "attributes",
+            &mut duplicates,
+        );
+        merge_section(&mut self.enums, extension.enums, "enums", &mut duplicates);
+        for external_type in extension.external_types {
+            if !self.external_types.contains(&external_type) {
+                self.external_types.push(external_type);
+            }
+        }
+        for child_type in extension.external_child_types {
+            if !self.external_child_types.contains(&child_type) {
+                self.external_child_types.push(child_type);
+            }
+        }
+        for option_aware_type in extension.option_aware_types {
+            if !self.option_aware_types.contains(&option_aware_type) {
+                self.option_aware_types.push(option_aware_type);
+            }
+        }
+        for color_type in extension.color_types {
+            if !self.color_types.contains(&color_type) {
+                self.color_types.push(color_type);
+            }
+        }
+        duplicates
+    }
+
+    /// Copies derived and shared attribute fields into every element and fills in enum defaults
+    pub(crate) fn resolve(&mut self) {
+        for element in self.elements.values_mut() {
+            let mut derives_queue: Vec<&String> = Vec::new();
+            derives_queue.extend(element.derives.iter());
+
+            let mut processed_derives: std::collections::HashSet<&String> =
+                std::collections::HashSet::new();
+            let mut all_derives: Vec<String> = Vec::new();
+
+            while let Some(derive_name) = derives_queue.pop() {
+                if !processed_derives.insert(derive_name) {
+                    continue;
+                }
+
+                all_derives.push(derive_name.clone());
+
+                if let Some(derivable) = &self.derives.get(derive_name) {
+                    for (field_name, field) in &derivable.fields {
+                        element.fields.insert(field_name.clone(), field.clone());
+                    }
+                    derives_queue.extend(derivable.derives.iter());
+                } else {
+                    panic!("Derivable {} not found", derive_name);
+                }
+            }
+
+            element.derives = all_derives;
+        }
+
+        for (attribute_name, attribute) in self.attributes.iter() {
+            for element in &attribute.elements {
+                self.elements
+                    .get_mut(element)
+                    .unwrap()
+                    .fields
+                    .insert(attribute_name.clone(), attribute.field.clone());
+            }
+        }
+
+        for element in self.elements.values_mut() {
+            for field in element.fields.values_mut() {
+                if field.default.is_none()
+                    && self
+                        .enums
+                        .get(&field.field_type)
+                        .is_some_and(|keyword_enum| keyword_enum.default.is_some())
+                {
+                    field.default = Some(format!("{}::default()", field.field_type));
+                }
+            }
+        }
+    }
+}
+
+fn merge_section<T>(
+    section: &mut BTreeMap<String, T>,
+    extension: BTreeMap<String, T>,
+    section_name: &'static str,
+    duplicates: &mut Vec<(&'static str, String)>,
+) {
+    for (key, value) in extension {
+        match section.entry(key) {
+            Entry::Occupied(entry) => duplicates.push((section_name, entry.key().clone())),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+        }
+    }
+}
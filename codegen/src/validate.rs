@@ -0,0 +1,578 @@
+use std::collections::BTreeMap;
+
+use crate::generate::{camel_to_snake, capitalize};
+use crate::schema::{Config, Element, Field};
+
+/// Methods generated on every element, which a field builder must not shadow
+const RESERVED_METHODS: [&str; 16] = [
+    "new",
+    "add_child",
+    "add_children",
+    "children",
+    "write_svg",
+    "to_svg_string",
+    "map_colors",
+    "tag_name",
+    "child_nodes",
+    "get_attribute",
+    "extra_attributes",
+    "attr",
+    "try_attr",
+    "data",
+    "try_data",
+    "default",
+];
+
+/// Types from the standard library that fields may use without listing them in `external_types`
+const PRIMITIVE_TYPES: [&str; 17] = [
+    "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64",
+    "usize", "String", "Vec", "Option",
+];
+
+/// Struct fields generated on every element
+const RESERVED_FIELDS: [&str; 2] = ["children", "extra_attributes"];
+
+/// Checks that every name in the schema resolves, so mistakes are reported against the YAML
+/// instead of as errors in the generated code
+pub(crate) fn validate_config(config: &Config, source: &SourceMap) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut report = |location: Option<Location>, message: String| {
+        problems.push(source.describe(location, &message))
+    };
+
+    let child_types = config
+        .element_types
+        .keys()
+        .cloned()
+        .chain(config.elements.keys().map(|name| capitalize(name)))
+        .chain(config.external_child_types.iter().cloned())
+        .collect::<Vec<_>>();
+
+    let known_types = PRIMITIVE_TYPES
+        .iter()
+        .map(|primitive| primitive.to_string())
+        .chain(config.enums.keys().cloned())
+        .chain(config.external_types.iter().cloned())
+        .chain(config.external_child_types.iter().cloned())
+        .chain(config.option_aware_types.iter().cloned())
+        .chain(config.color_types.iter().cloned())
+        .collect::<Vec<_>>();
+
+    let mut struct_names = BTreeMap::new();
+    for element_name in config.elements.keys() {
+        if let Some(other) = struct_names.insert(capitalize(element_name), element_name) {
+            report(
+                source.entry("elements", element_name),
+                format!(
+                    "elements `{}` and `{}` both generate the struct `{}`",
+                    other,
+                    element_name,
+                    capitalize(element_name)
+                ),
+            );
+        }
+    }
+
+    for (element_name, element) in &config.elements {
+        for category in &element.element_types {
+            if !config.element_types.contains_key(category) {
+                report(
+                    source.within("elements", element_name, category),
+                    format!(
+                        "element `{}` has unknown category `{}`{}",
+                        element_name,
+                        category,
+                        suggest(category, config.element_types.keys())
+                    ),
+                );
+            }
+        }
+        for child_type in &element.valid_child_types {
+            if !child_types.contains(child_type) {
+                report(
+                    source.within("elements", element_name, child_type),
+                    format!(
+                        "element `{}` allows unknown child type `{}`, expected a category, a capitalized element name or one of `external_child_types`{}",
+                        element_name,
+                        child_type,
+                        suggest(child_type, child_types.iter())
+                    ),
+                );
+            }
+        }
+        for derive in &element.derives {
+            if !config.derives.contains_key(derive) {
+                report(
+                    source.within("elements", element_name, derive),
+                    format!(
+                        "element `{}` derives unknown `{}`{}",
+                        element_name,
+                        derive,
+                        suggest(derive, config.derives.keys())
+                    ),
+                );
+            }
+        }
+        for (field_name, field) in &element.fields {
+            if let Err(message) = validate_type(&field.field_type, &known_types) {
+                report(
+                    source.within("elements", element_name, field_name),
+                    format!(
+                        "field `{}` of element `{}` has invalid type `{}`: {}",
+                        field_name, element_name, field.field_type, message
+                    ),
+                );
+            }
+        }
+    }
+
+    for (derive_name, derivable) in &config.derives {
+        for derive in &derivable.derives {
+            if !config.derives.contains_key(derive) {
+                report(
+                    source.within("derives", derive_name, derive),
+                    format!(
+                        "`{}` derives unknown `{}`{}",
+                        derive_name,
+                        derive,
+                        suggest(derive, config.derives.keys())
+                    ),
+                );
+            }
+        }
+        for (field_name, field) in &derivable.fields {
+            if let Err(message) = validate_type(&field.field_type, &known_types) {
+                report(
+                    source.within("derives", derive_name, field_name),
+                    format!(
+                        "field `{}` of `{}` has invalid type `{}`: {}",
+                        field_name, derive_name, field.field_type, message
+                    ),
+                );
+            }
+        }
+    }
+
+    for (enum_name, keyword_enum) in &config.enums {
+        let mut names = std::collections::HashSet::new();
+        for variant in &keyword_enum.variants {
+            let name = variant.name();
+            if syn::parse_str::<syn::Ident>(&name).is_err() {
+                report(
+                    source.within("enums", enum_name, variant.keyword()),
+                    format!(
+                        "keyword `{}` of `{}` becomes `{}`, which is not a valid variant name, give it an explicit `name`",
+                        variant.keyword(),
+                        enum_name,
+                        name
+                    ),
+                );
+            } else if !names.insert(name.clone()) {
+                report(
+                    source.within("enums", enum_name, variant.keyword()),
+                    format!("`{}` has more than one variant named `{}`", enum_name, name),
+                );
+            }
+        }
+        if let Some(default) = &keyword_enum.default
+            && !keyword_enum
+                .variants
+                .iter()
+                .any(|variant| variant.keyword() == default)
+        {
+            report(
+                source.within("enums", enum_name, "default"),
+                format!(
+                    "default `{}` of `{}` is not one of its keywords",
+                    default, enum_name
+                ),
+            );
+        }
+    }
+
+    for (attribute_name, attribute) in &config.attributes {
+        if let Err(message) = validate_type(&attribute.field.field_type, &known_types) {
+            report(
+                source.entry("attributes", attribute_name),
+                format!(
+                    "attribute `{}` has invalid type `{}`: {}",
+                    attribute_name, attribute.field.field_type, message
+                ),
+            );
+        }
+        for element_name in &attribute.elements {
+            if !config.elements.contains_key(element_name) {
+                report(
+                    source.within("attributes", attribute_name, element_name),
+                    format!(
+                        "attribute `{}` targets unknown element `{}`{}",
+                        attribute_name,
+                        element_name,
+                        suggest(element_name, config.elements.keys())
+                    ),
+                );
+            }
+        }
+    }
+
+    for (element_name, element) in &config.elements {
+        let mut identifiers: BTreeMap<String, &String> = BTreeMap::new();
+        let mut attribute_names: BTreeMap<&str, &String> = BTreeMap::new();
+        let fields = merged_field_names(config, element_name, element);
+        for &(field_name, field, section, owner) in &fields {
+            let line = source.within(section, owner, field_name);
+            let attribute_name = field.attribute_name(field_name);
+            if let Some(other) = attribute_names.insert(attribute_name, field_name)
+                && other != field_name
+            {
+                report(
+                    line,
+                    format!(
+                        "fields `{}` and `{}` of element `{}` both write the `{}` attribute",
+                        other, field_name, element_name, attribute_name
+                    ),
+                );
+            }
+            if let Some(default) = &field.default
+                && let Err(error) = syn::parse_str::<syn::Expr>(default)
+            {
+                report(
+                    line,
+                    format!(
+                        "field `{}` of element `{}` has invalid default `{}`: {}",
+                        field_name, element_name, default, error
+                    ),
+                );
+            }
+            if field.is_css() && field.field_type != "String" {
+                report(
+                    line,
+                    format!(
+                        "field `{}` of element `{}` sets `is_css` but has type `{}` instead of `String`",
+                        field_name, element_name, field.field_type
+                    ),
+                );
+            }
+            let identifier = camel_to_snake(field_name);
+            if syn::parse_str::<syn::Ident>(&identifier).is_err() {
+                report(
+                    line,
+                    format!(
+                        "field `{}` of element `{}` becomes `{}`, which is not a valid Rust identifier",
+                        field_name, element_name, identifier
+                    ),
+                );
+            } else if RESERVED_FIELDS.contains(&identifier.as_str())
+                || RESERVED_METHODS.contains(&identifier.as_str())
+                || identifier.starts_with("add_child_")
+            {
+                report(
+                    line,
+                    format!(
+                        "field `{}` of element `{}` collides with the generated `{}`",
+                        field_name, element_name, identifier
+                    ),
+                );
+            } else if let Some(other) = identifiers.insert(identifier.clone(), field_name)
+                && other != field_name
+            {
+                report(
+                    line,
+                    format!(
+                        "fields `{}` and `{}` of element `{}` both generate `{}`",
+                        other, field_name, element_name, identifier
+                    ),
+                );
+            }
+        }
+
+        if element.is_stylesheet
+            && !element
+                .valid_child_types
+                .iter()
+                .any(|child| child == "String")
+        {
+            report(
+                source.entry("elements", element_name),
+                format!(
+                    "element `{}` sets `is_stylesheet` but does not take `String` children",
+                    element_name
+                ),
+            );
+        }
+
+        for (index, required) in element.required.iter().enumerate() {
+            let line = source.within("elements", element_name, required);
+            let field = fields
+                .iter()
+                .find(|(field_name, ..)| *field_name == required)
+                .map(|(_, field, ..)| field);
+            if element.required[..index].contains(required) {
+                report(
+                    line,
+                    format!(
+                        "`{}` is required more than once by element `{}`",
+                        required, element_name
+                    ),
+                );
+            } else if let Some(field) = field {
+                if field.is_experimental.unwrap_or(false) {
+                    report(
+                        line,
+                        format!(
+                            "required field `{}` of element `{}` can't be experimental",
+                            required, element_name
+                        ),
+                    );
+                } else if field.default.is_some() {
+                    report(
+                        line,
+                        format!(
+                            "required field `{}` of element `{}` can't have a default",
+                            required, element_name
+                        ),
+                    );
+                }
+            } else {
+                report(
+                    line,
+                    format!(
+                        "element `{}` requires unknown field `{}`{}",
+                        element_name,
+                        required,
+                        suggest(required, fields.iter().map(|(field_name, ..)| *field_name))
+                    ),
+                );
+            }
+        }
+    }
+
+    problems
+}
+
+/// Every field an element ends up with, along with the section and entry defining it
+fn merged_field_names<'a>(
+    config: &'a Config,
+    element_name: &'a str,
+    element: &'a Element,
+) -> Vec<(&'a String, &'a Field, &'static str, &'a str)> {
+    let mut fields = element
+        .fields
+        .iter()
+        .map(|(field_name, field)| (field_name, field, "elements", element_name))
+        .collect::<Vec<_>>();
+
+    let mut queue = element.derives.iter().collect::<Vec<_>>();
+    let mut visited = std::collections::HashSet::new();
+    while let Some(derive_name) = queue.pop() {
+        if !visited.insert(derive_name) {
+            continue;
+        }
+        if let Some(derivable) = config.derives.get(derive_name) {
+            fields.extend(
+                derivable.fields.iter().map(|(field_name, field)| {
+                    (field_name, field, "derives", derive_name.as_str())
+                }),
+            );
+            queue.extend(derivable.derives.iter());
+        }
+    }
+
+    for (attribute_name, attribute) in &config.attributes {
+        if attribute
+            .elements
+            .iter()
+            .any(|target| target == element_name)
+        {
+            fields.push((
+                attribute_name,
+                &attribute.field,
+                "attributes",
+                attribute_name.as_str(),
+            ));
+        }
+    }
+    fields
+}
+
+/// Checks that a field type parses and that it and its generic arguments are known types
+fn validate_type(field_type: &str, known_types: &[String]) -> Result<(), String> {
+    let parsed = syn::parse_str::<syn::Type>(field_type).map_err(|error| error.to_string())?;
+    check_type_names(&parsed, known_types)
+}
+
+fn check_type_names(field_type: &syn::Type, known_types: &[String]) -> Result<(), String> {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = field_type else {
+        return Err("expected a type name such as `f64` or `List<String>`".to_string());
+    };
+    let name = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::");
+    if !known_types.contains(&name) {
+        return Err(format!(
+            "unknown type `{}`, expected an enum, a Rust primitive or one of `external_types`{}",
+            name,
+            suggest(&name, known_types.iter())
+        ));
+    }
+    for segment in &path.segments {
+        if let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments {
+            for argument in &arguments.args {
+                if let syn::GenericArgument::Type(argument) = argument {
+                    check_type_names(argument, known_types)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A ", did you mean `x`?" hint for the closest candidate, if any is close enough
+fn suggest<'a>(name: &str, candidates: impl Iterator<Item = &'a String>) -> String {
+    let name = name.to_lowercase();
+    candidates
+        .map(|candidate| {
+            let lowercase = candidate.to_lowercase();
+            let distance = if lowercase.starts_with(&name) {
+                0
+            } else {
+                edit_distance(&name, &lowercase)
+            };
+            (distance, candidate)
+        })
+        .filter(|(distance, _)| *distance <= 3)
+        .min()
+        .map(|(_, candidate)| format!(", did you mean `{}`?", candidate))
+        .unwrap_or_default()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// A position in one of the schema files
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Location {
+    file: usize,
+    line: usize,
+}
+
+/// Finds line numbers of schema entries, which serde_yaml does not keep track of. Later files
+/// are searched first, so entries an extension defines are reported against the extension
+#[derive(Default)]
+pub(crate) struct SourceMap<'a> {
+    files: Vec<SourceFile<'a>>,
+}
+
+impl<'a> SourceMap<'a> {
+    pub(crate) fn add(&mut self, name: &'a str, yaml: &'a str) {
+        self.files.push(SourceFile::new(name, yaml));
+    }
+
+    /// Prefixes `message` with `file:line`, or with the last file when there is no location
+    pub(crate) fn describe(&self, location: Option<Location>, message: &str) -> String {
+        match location {
+            Some(location) => format!(
+                "{}:{}: {}",
+                self.files[location.file].name, location.line, message
+            ),
+            None => match self.files.last() {
+                Some(file) => format!("{}: {}", file.name, message),
+                None => message.to_string(),
+            },
+        }
+    }
+
+    pub(crate) fn entry(&self, section: &str, key: &str) -> Option<Location> {
+        self.find(|file| file.entry(section, key))
+    }
+
+    pub(crate) fn within(&self, section: &str, key: &str, needle: &str) -> Option<Location> {
+        self.find(|file| file.within(section, key, needle))
+    }
+
+    fn find(&self, search: impl Fn(&SourceFile) -> Option<usize>) -> Option<Location> {
+        self.files
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(file, source)| search(source).map(|line| Location { file, line }))
+    }
+}
+
+struct SourceFile<'a> {
+    name: &'a str,
+    lines: Vec<&'a str>,
+}
+
+impl<'a> SourceFile<'a> {
+    fn new(name: &'a str, yaml: &'a str) -> Self {
+        Self {
+            name,
+            lines: yaml
+                .lines()
+                .map(|line| line.split(" #").next().unwrap_or_default())
+                .collect(),
+        }
+    }
+
+    fn is_content(line: &str) -> bool {
+        !line.trim().is_empty() && !line.trim_start().starts_with('#')
+    }
+
+    fn indentation(line: &str) -> usize {
+        line.len() - line.trim_start().len()
+    }
+
+    /// The 1-based line of `key` directly under the top level `section`
+    fn entry(&self, section: &str, key: &str) -> Option<usize> {
+        let section_header = format!("{}:", section);
+        let start = self
+            .lines
+            .iter()
+            .position(|line| line.trim_end() == section_header)?;
+        let entry_header = format!("{}:", key);
+        self.lines[start + 1..]
+            .iter()
+            .take_while(|line| !Self::is_content(line) || Self::indentation(line) > 0)
+            .position(|line| {
+                Self::is_content(line)
+                    && Self::indentation(line) == 2
+                    && line.trim_start().starts_with(&entry_header)
+            })
+            .map(|offset| start + offset + 2)
+    }
+
+    /// The 1-based line mentioning `needle` inside the block of `key`, falling back to the key itself
+    fn within(&self, section: &str, key: &str, needle: &str) -> Option<usize> {
+        let entry = self.entry(section, key)?;
+        let block_end = self.lines[entry..]
+            .iter()
+            .position(|line| Self::is_content(line) && Self::indentation(line) <= 2)
+            .map_or(self.lines.len(), |offset| entry + offset);
+        let mentions = |line: &&str| {
+            let line = line.trim_start().trim_start_matches("- ");
+            line.contains(&format!("\"{}\"", needle))
+                || line.starts_with(&format!("{}:", needle))
+                || line.trim_end_matches(',') == needle
+        };
+        let mut lines = self.lines[entry - 1..block_end].iter();
+        let found = lines
+            .position(|line| Self::is_content(line) && mentions(line))
+            .map(|offset| entry + offset);
+        found.or(Some(entry))
+    }
+}
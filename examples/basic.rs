@@ -3,26 +3,20 @@ use easy_svg::types::{Color, PreserveAspectRatio};
 
 fn main() {
     let svg = Svg::new()
-        .view_box((0., 0., 500., 500.))
-        .preserve_aspect_ratio(PreserveAspectRatio::XMidYMid)
         .width(600.)
         .height(600.)
-        .add_child_shape_element(
-            Rect::new()
-                .width(200.)
-                .height(400.)
-                .x(20.)
-                .fill(Color::DarkOliveGreen),
-        )
-        .add_child_text(
+        .view_box((0., 0., 500., 500.))
+        .preserve_aspect_ratio(PreserveAspectRatio::XMidYMid)
+        .add_child(Rect::new(200., 400.).x(20.).fill(Color::DarkOliveGreen))
+        .add_child(
             Text::new()
                 .x(30.)
                 .y(70.)
                 .fill(Color::DarkMagenta)
-                .add_child_string("Hello World".to_string())
+                .add_child("Hello World".to_string())
                 .font_family("Arial".to_string()),
         )
-        .add_child_shape_element(Circle::new().fill(Color::DarkBlue).r(20.).cx(80.).cy(85.));
+        .add_child(Circle::new(20.).fill(Color::DarkBlue).cx(80.).cy(85.));
 
     println!("{}", svg);
 }
@@ -0,0 +1,216 @@
+//! The `svg!` macro, re-exported as `easy_svg::svg`.
+
+mod parse;
+
+use easy_svg_codegen::{ElementSchema, Schema};
+use parse::{Element, Else, Node};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use std::sync::OnceLock;
+use syn::spanned::Spanned;
+
+/// Builds an element from markup, expanding to the element's builders.
+///
+/// ```ignore
+/// let svg = svg! {
+///     <svg width=500 height=500>
+///         <rect x=20 width=200 height=400 fill=Color::DarkOliveGreen/>
+///         <text x=30 y=70>"Hello World"</text>
+///         for radius in [10., 20.] {
+///             <circle r={radius}/>
+///         }
+///         {legend}
+///     </svg>
+/// };
+/// ```
+///
+/// Attribute values are literals, paths such as `Color::Red`, calls such as `Paint::url("p")`
+/// or `{expressions}`. Children are elements, string literals, `{expressions}` added through
+/// `add_child`, `for` loops and `if`/`else`. Unknown elements and attributes, values of the
+/// wrong type and children the parent does not accept are compile errors pointing at the markup.
+/// Only the built-in elements are known, extension elements can be added as `{expressions}`.
+/// Experimental attributes are rejected unless easy-svg's `experimental` feature is enabled
+#[proc_macro]
+pub fn svg(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let root = syn::parse_macro_input!(input as Element);
+    expand_element(&root)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn schema() -> &'static Schema {
+    static SCHEMA: OnceLock<Schema> = OnceLock::new();
+    SCHEMA.get_or_init(Schema::builtin)
+}
+
+fn element_schema(element: &Element) -> syn::Result<ElementSchema<'static>> {
+    schema().element(&element.name).ok_or_else(|| {
+        syn::Error::new(
+            element.span,
+            format!("`<{}>` is not an SVG element easy-svg knows", element.name),
+        )
+    })
+}
+
+fn expand_element(element: &Element) -> syn::Result<TokenStream> {
+    let element_schema = element_schema(element)?;
+    let struct_ident = format_ident!("{}", element_schema.struct_name(), span = element.span);
+
+    let mut required = Vec::new();
+    for required_name in element_schema.required_attributes() {
+        let attribute = element
+            .attributes
+            .iter()
+            .find(|attribute| attribute.name == required_name)
+            .ok_or_else(|| {
+                syn::Error::new(
+                    element.span,
+                    format!(
+                        "`<{}>` is missing the required attribute `{}`",
+                        element.name, required_name
+                    ),
+                )
+            })?;
+        required.push(&attribute.value);
+    }
+
+    let mut calls = Vec::new();
+    let mut seen = Vec::new();
+    for attribute in &element.attributes {
+        if seen.contains(&&attribute.name) {
+            return Err(syn::Error::new(
+                attribute.span,
+                format!("`{}` is set more than once", attribute.name),
+            ));
+        }
+        seen.push(&attribute.name);
+        if element_schema
+            .required_attributes()
+            .any(|required_name| required_name == attribute.name)
+        {
+            continue;
+        }
+
+        let value = &attribute.value;
+        if let Some(key) = attribute.name.strip_prefix("data-") {
+            calls.push(quote_spanned! {attribute.span=> .data(#key, #value) });
+            continue;
+        }
+        let builder = element_schema.builder(&attribute.name).ok_or_else(|| {
+            syn::Error::new(
+                attribute.span,
+                format!("`<{}>` has no attribute `{}`", element.name, attribute.name),
+            )
+        })?;
+        if element_schema.is_experimental(&attribute.name) && !cfg!(feature = "experimental") {
+            return Err(syn::Error::new(
+                attribute.span,
+                format!(
+                    "`{}` is experimental and needs the `experimental` feature of easy-svg",
+                    attribute.name
+                ),
+            ));
+        }
+        let builder_ident = format_ident!("{}", builder, span = attribute.span);
+        calls.push(quote! { .#builder_ident(#value) });
+    }
+
+    for child in &element.children {
+        calls.push(match child {
+            Node::For { .. } | Node::If { .. } => {
+                let children = children_ident();
+                let statements = expand_statement(&element_schema, child)?;
+                quote! { .children(|#children| { #statements }) }
+            }
+            _ => {
+                let (value, span) = expand_child(&element_schema, child)?;
+                quote_spanned! {span=> .add_child(#value) }
+            }
+        });
+    }
+
+    Ok(quote! {
+        ::easy_svg::elements::#struct_ident::new(#( #required ),*) #( #calls )*
+    })
+}
+
+/// The value of an element, text or `{expression}` child, checked against the parent's
+/// content model where it is known
+fn expand_child(parent: &ElementSchema, child: &Node) -> syn::Result<(TokenStream, Span)> {
+    match child {
+        Node::Element(element) => {
+            element_schema(element)?;
+            if !parent.allows_child(&element.name) {
+                return Err(syn::Error::new(
+                    element.span,
+                    format!(
+                        "`<{}>` is not a valid child of `<{}>`",
+                        element.name,
+                        parent.tag_name()
+                    ),
+                ));
+            }
+            Ok((expand_element(element)?, element.span))
+        }
+        Node::Text(text) => {
+            if !parent.allows_text() {
+                return Err(syn::Error::new(
+                    text.span(),
+                    format!("`<{}>` does not accept text", parent.tag_name()),
+                ));
+            }
+            Ok((
+                quote_spanned! {text.span()=> ::std::string::String::from(#text) },
+                text.span(),
+            ))
+        }
+        Node::Expr(expr) => Ok((quote! { #expr }, expr.span())),
+        Node::For { .. } | Node::If { .. } => unreachable!("loops and conditionals are statements"),
+    }
+}
+
+/// A child as a statement inside a `children` closure
+fn expand_statement(parent: &ElementSchema, node: &Node) -> syn::Result<TokenStream> {
+    let children = children_ident();
+    match node {
+        Node::For { pat, expr, body } => {
+            let body = expand_statements(parent, body)?;
+            Ok(quote! { for #pat in #expr { #body } })
+        }
+        Node::If {
+            condition,
+            then,
+            otherwise,
+        } => {
+            let then = expand_statements(parent, then)?;
+            let otherwise = match otherwise {
+                Some(Else::Block(nodes)) => {
+                    let nodes = expand_statements(parent, nodes)?;
+                    quote! { else { #nodes } }
+                }
+                Some(Else::If(node)) => {
+                    let node = expand_statement(parent, node)?;
+                    quote! { else #node }
+                }
+                None => quote! {},
+            };
+            Ok(quote! { if #condition { #then } #otherwise })
+        }
+        _ => {
+            let (value, span) = expand_child(parent, node)?;
+            Ok(quote_spanned! {span=> #children.add(#value); })
+        }
+    }
+}
+
+fn expand_statements(parent: &ElementSchema, nodes: &[Node]) -> syn::Result<TokenStream> {
+    nodes
+        .iter()
+        .map(|node| expand_statement(parent, node))
+        .collect()
+}
+
+/// The `Children` collector inside generated closures, unnameable from the macro input
+fn children_ident() -> proc_macro2::Ident {
+    proc_macro2::Ident::new("children", Span::mixed_site())
+}
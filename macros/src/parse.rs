@@ -0,0 +1,211 @@
+use proc_macro2::Span;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, Lit, LitStr, Pat, Token, braced, parenthesized, token};
+
+/// Markup between `<` and `>`, or one of the Rust forms allowed between elements
+pub(crate) enum Node {
+    Element(Element),
+    Text(LitStr),
+    /// `{expression}`, added as a single child
+    Expr(Expr),
+    For {
+        pat: Pat,
+        expr: Expr,
+        body: Vec<Node>,
+    },
+    If {
+        condition: Expr,
+        then: Vec<Node>,
+        otherwise: Option<Else>,
+    },
+}
+
+pub(crate) enum Else {
+    Block(Vec<Node>),
+    If(Box<Node>),
+}
+
+pub(crate) struct Element {
+    pub(crate) name: String,
+    pub(crate) span: Span,
+    pub(crate) attributes: Vec<Attribute>,
+    pub(crate) children: Vec<Node>,
+}
+
+pub(crate) struct Attribute {
+    pub(crate) name: String,
+    pub(crate) span: Span,
+    pub(crate) value: Expr,
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![<]) {
+            return input.parse().map(Node::Element);
+        }
+        if input.peek(LitStr) {
+            return input.parse().map(Node::Text);
+        }
+        if input.peek(token::Brace) {
+            return parse_block(input).map(Node::Expr);
+        }
+        if input.peek(Token![for]) {
+            input.parse::<Token![for]>()?;
+            let pat = Pat::parse_single(input)?;
+            input.parse::<Token![in]>()?;
+            let expr = Expr::parse_without_eager_brace(input)?;
+            let body = parse_nodes_in_braces(input)?;
+            return Ok(Node::For { pat, expr, body });
+        }
+        if input.peek(Token![if]) {
+            input.parse::<Token![if]>()?;
+            let condition = Expr::parse_without_eager_brace(input)?;
+            let then = parse_nodes_in_braces(input)?;
+            let otherwise = if input.parse::<Option<Token![else]>>()?.is_some() {
+                if input.peek(Token![if]) {
+                    Some(Else::If(Box::new(input.parse()?)))
+                } else {
+                    Some(Else::Block(parse_nodes_in_braces(input)?))
+                }
+            } else {
+                None
+            };
+            return Ok(Node::If {
+                condition,
+                then,
+                otherwise,
+            });
+        }
+        Err(input.error(
+            "expected an element, a string literal, `{expression}`, a `for` loop or an `if`",
+        ))
+    }
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let (name, span) = parse_name(input)?;
+
+        let mut attributes = Vec::new();
+        while !input.peek(Token![/]) && !input.peek(Token![>]) {
+            if input.is_empty() {
+                return Err(input.error(format!("`<{}>` is missing its closing `>`", name)));
+            }
+            attributes.push(input.parse()?);
+        }
+
+        if input.parse::<Option<Token![/]>>()?.is_some() {
+            input.parse::<Token![>]>()?;
+            return Ok(Element {
+                name,
+                span,
+                attributes,
+                children: Vec::new(),
+            });
+        }
+        input.parse::<Token![>]>()?;
+
+        let mut children = Vec::new();
+        while !(input.peek(Token![<]) && input.peek2(Token![/])) {
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    span,
+                    format!("`<{}>` is never closed, expected `</{}>`", name, name),
+                ));
+            }
+            children.push(input.parse()?);
+        }
+        input.parse::<Token![<]>()?;
+        input.parse::<Token![/]>()?;
+        let (closing_name, closing_span) = parse_name(input)?;
+        if closing_name != name {
+            return Err(syn::Error::new(
+                closing_span,
+                format!("expected `</{}>`, found `</{}>`", name, closing_name),
+            ));
+        }
+        input.parse::<Token![>]>()?;
+
+        Ok(Element {
+            name,
+            span,
+            attributes,
+            children,
+        })
+    }
+}
+
+impl Parse for Attribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (name, span) = parse_name(input)?;
+        input.parse::<Token![=]>()?;
+        let value = parse_value(input)?;
+        Ok(Attribute { name, span, value })
+    }
+}
+
+/// A tag or attribute name, which may contain dashes such as `stroke-width`
+fn parse_name(input: ParseStream) -> syn::Result<(String, Span)> {
+    let first = Ident::parse_any(input)?;
+    let mut name = first.unraw().to_string();
+    let mut span = first.span();
+    while input.peek(Token![-]) {
+        input.parse::<Token![-]>()?;
+        let next = Ident::parse_any(input)?;
+        name.push('-');
+        name.push_str(&next.unraw().to_string());
+        span = span.join(next.span()).unwrap_or(span);
+    }
+    Ok((name, span))
+}
+
+/// A literal, `{expression}` or a path such as `Color::Red`, optionally called with arguments
+fn parse_value(input: ParseStream) -> syn::Result<Expr> {
+    if input.peek(token::Brace) {
+        return parse_block(input);
+    }
+    if input.peek(Token![-]) {
+        let minus = input.parse::<Token![-]>()?;
+        let lit: Lit = input.parse()?;
+        return Ok(syn::parse_quote!(#minus #lit));
+    }
+    if input.peek(Lit) {
+        let lit: Lit = input.parse()?;
+        return Ok(syn::parse_quote!(#lit));
+    }
+
+    let path: syn::ExprPath = input.parse()?;
+    if !input.peek(token::Paren) {
+        return Ok(Expr::Path(path));
+    }
+    let content;
+    parenthesized!(content in input);
+    let arguments = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+    Ok(syn::parse_quote!(#path(#arguments)))
+}
+
+/// `{ ... }`, unwrapped when it holds a single expression
+fn parse_block(input: ParseStream) -> syn::Result<Expr> {
+    let block: syn::Block = input.parse()?;
+    if let [syn::Stmt::Expr(expr, None)] = block.stmts.as_slice() {
+        return Ok(expr.clone());
+    }
+    Ok(Expr::Block(syn::ExprBlock {
+        attrs: Vec::new(),
+        label: None,
+        block,
+    }))
+}
+
+fn parse_nodes_in_braces(input: ParseStream) -> syn::Result<Vec<Node>> {
+    let content;
+    braced!(content in input);
+    let mut nodes = Vec::new();
+    while !content.is_empty() {
+        nodes.push(content.parse()?);
+    }
+    Ok(nodes)
+}
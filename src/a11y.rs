@@ -0,0 +1,349 @@
+//! Accessibility checks for finished documents.
+//!
+//! The checks are heuristics over the element tree: they catch graphics that screen readers
+//! cannot announce and text that is hard to read, but do not replace testing with real
+//! assistive technology. Text is compared against the last solid `rect`, `circle` or `ellipse`
+//! painted under its start, ignoring transforms and nested viewports.
+
+use crate::Shape;
+use crate::types::{AriaRole, Color};
+use std::fmt::Display;
+
+/// Roles that are not announced meaningfully without an accessible name
+const NAMED_ROLES: [AriaRole; 10] = [
+    AriaRole::GraphicsDocument,
+    AriaRole::GraphicsObject,
+    AriaRole::GraphicsSymbol,
+    AriaRole::Img,
+    AriaRole::Figure,
+    AriaRole::Link,
+    AriaRole::Button,
+    AriaRole::Heading,
+    AriaRole::Table,
+    AriaRole::Region,
+];
+
+/// WCAG 2 AA minimum contrast for regular text
+pub const MIN_CONTRAST: f64 = 4.5;
+/// WCAG 2 AA minimum contrast for large text, 18pt (24px) and up, or 14pt (18.67px) and up
+/// when bold
+pub const MIN_CONTRAST_LARGE_TEXT: f64 = 3.;
+const LARGE_TEXT_PX: f64 = 24.;
+const LARGE_BOLD_TEXT_PX: f64 = 14. * 4. / 3.;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum A11yIssue {
+    /// A graphic has no `title` child, `aria-label` or `aria-labelledby`
+    MissingAccessibleName {
+        tag_name: String,
+        id: Option<String>,
+    },
+    /// Text fill does not contrast enough with the fill behind it
+    InsufficientContrast {
+        tag_name: String,
+        id: Option<String>,
+        ratio: f64,
+        required: f64,
+    },
+}
+
+impl Display for A11yIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let describe = |tag_name: &str, id: &Option<String>| match id {
+            Some(id) => format!("<{} id=\"{}\">", tag_name, id),
+            None => format!("<{}>", tag_name),
+        };
+        match self {
+            A11yIssue::MissingAccessibleName { tag_name, id } => write!(
+                f,
+                "{} has no accessible name, add a `title` child or `aria-label`",
+                describe(tag_name, id)
+            ),
+            A11yIssue::InsufficientContrast {
+                tag_name,
+                id,
+                ratio,
+                required,
+            } => write!(
+                f,
+                "{} has a contrast ratio of {:.2}:1 against the background, at least {}:1 is required",
+                describe(tag_name, id),
+                ratio,
+                required
+            ),
+        }
+    }
+}
+
+/// Configurable accessibility check, see [`check`] for the defaults
+#[derive(Debug, Clone)]
+pub struct A11yCheck {
+    background: Color,
+}
+
+impl Default for A11yCheck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl A11yCheck {
+    pub fn new() -> Self {
+        Self {
+            background: Color::White,
+        }
+    }
+
+    /// The color behind text that no filled shape lies under, white by default
+    pub fn background(mut self, background: Color) -> Self {
+        self.background = background;
+        self
+    }
+
+    pub fn check(&self, shape: &Shape) -> Vec<A11yIssue> {
+        let mut issues = Vec::new();
+        if shape.tag_name() == Some("svg") && accessible_name(shape).is_none() {
+            issues.push(missing_name(shape));
+        }
+        let mut backdrops = Vec::new();
+        self.visit(shape, &Style::default(), &mut backdrops, &mut issues);
+        issues
+    }
+
+    fn visit(
+        &self,
+        shape: &Shape,
+        inherited: &Style,
+        backdrops: &mut Vec<Backdrop>,
+        issues: &mut Vec<A11yIssue>,
+    ) {
+        if shape.get_attribute("aria-hidden").as_deref() == Some("true") {
+            return;
+        }
+
+        if let Some(role) = shape.get_attribute("role")
+            && NAMED_ROLES.iter().any(|named| named.to_string() == role)
+            && accessible_name(shape).is_none()
+        {
+            issues.push(missing_name(shape));
+        }
+
+        let style = inherited.inherit(shape);
+        if has_text(shape) {
+            if let Some(issue) = self.check_contrast(shape, &style, backdrops) {
+                issues.push(issue);
+            }
+        } else if let Some(backdrop) = self.backdrop(shape, &style, backdrops) {
+            backdrops.push(backdrop);
+        }
+
+        for child in shape.child_nodes() {
+            self.visit(child, &style, backdrops, issues);
+        }
+    }
+
+    /// The fill behind `point`, from the last shape painted there
+    fn background_at(&self, [x, y]: [f64; 2], backdrops: &[Backdrop]) -> Color {
+        backdrops
+            .iter()
+            .rev()
+            .find(|backdrop| {
+                let [min_x, min_y, max_x, max_y] = backdrop.bounds;
+                (min_x..=max_x).contains(&x) && (min_y..=max_y).contains(&y)
+            })
+            .map_or_else(
+                || self.background.clone(),
+                |backdrop| backdrop.color.clone(),
+            )
+    }
+
+    /// The area a `rect`, `circle` or `ellipse` fills with a solid color
+    fn backdrop(&self, shape: &Shape, style: &Style, backdrops: &[Backdrop]) -> Option<Backdrop> {
+        let number = |name: &str| {
+            shape
+                .get_attribute(name)
+                .and_then(|value| parse_number(&value))
+        };
+        let bounds = match shape.tag_name()? {
+            "rect" => {
+                let [x, y] = [number("x").unwrap_or(0.), number("y").unwrap_or(0.)];
+                [x, y, x + number("width")?, y + number("height")?]
+            }
+            "circle" | "ellipse" => {
+                let [cx, cy] = [number("cx").unwrap_or(0.), number("cy").unwrap_or(0.)];
+                let rx = number("rx").or_else(|| number("r"))?;
+                let ry = number("ry").or_else(|| number("r")).unwrap_or(rx);
+                [cx - rx, cy - ry, cx + rx, cy + ry]
+            }
+            _ => return None,
+        };
+        let fill = style.fill.as_ref()?;
+        let [min_x, min_y, max_x, max_y] = bounds;
+        let below = self.background_at([(min_x + max_x) / 2., (min_y + max_y) / 2.], backdrops);
+        Some(Backdrop {
+            bounds,
+            color: composite(fill, &below)?,
+        })
+    }
+
+    fn check_contrast(
+        &self,
+        shape: &Shape,
+        style: &Style,
+        backdrops: &[Backdrop],
+    ) -> Option<A11yIssue> {
+        let fill = style.fill.as_ref()?;
+        // the middle of the first glyphs, which sit above the baseline at `y`
+        let [x, y] = style.position;
+        let background = self.background_at([x, y - style.font_size_px / 3.], backdrops);
+        let ratio = composite(fill, &background)?.contrast_ratio(&background)?;
+        let is_large = style.font_size_px >= LARGE_TEXT_PX
+            || (style.is_bold && style.font_size_px >= LARGE_BOLD_TEXT_PX);
+        let required = if is_large {
+            MIN_CONTRAST_LARGE_TEXT
+        } else {
+            MIN_CONTRAST
+        };
+        if ratio >= required {
+            return None;
+        }
+        Some(A11yIssue::InsufficientContrast {
+            tag_name: shape.tag_name().unwrap_or_default().to_string(),
+            id: shape.get_attribute("id"),
+            ratio,
+            required,
+        })
+    }
+}
+
+/// Runs [`A11yCheck`] against a white background
+pub fn check(shape: &Shape) -> Vec<A11yIssue> {
+    A11yCheck::new().check(shape)
+}
+
+/// The accessible name from `aria-label`, `aria-labelledby` or a `title` child
+pub fn accessible_name(shape: &Shape) -> Option<String> {
+    let non_empty = |name: String| (!name.trim().is_empty()).then_some(name);
+    shape
+        .get_attribute("aria-label")
+        .and_then(non_empty)
+        .or_else(|| shape.get_attribute("aria-labelledby").and_then(non_empty))
+        .or_else(|| {
+            shape
+                .child_nodes()
+                .iter()
+                .find(|child| child.tag_name() == Some("title"))
+                .and_then(|title| non_empty(text_content(title)))
+        })
+}
+
+fn missing_name(shape: &Shape) -> A11yIssue {
+    A11yIssue::MissingAccessibleName {
+        tag_name: shape.tag_name().unwrap_or_default().to_string(),
+        id: shape.get_attribute("id"),
+    }
+}
+
+fn text_content(shape: &Shape) -> String {
+    match shape {
+        Shape::String(string) => string.clone(),
+        _ => shape.child_nodes().iter().map(text_content).collect(),
+    }
+}
+
+fn has_text(shape: &Shape) -> bool {
+    matches!(shape.tag_name(), Some("text" | "tspan" | "textPath"))
+        && shape
+            .child_nodes()
+            .iter()
+            .any(|child| matches!(child, Shape::String(string) if !string.trim().is_empty()))
+}
+
+/// Flattens a translucent color onto an opaque background
+fn composite(color: &Color, background: &Color) -> Option<Color> {
+    let [r, g, b, a] = color.to_srgba()?;
+    let [br, bg, bb, _] = background.to_srgba()?;
+    let channel = |fg: f64, bg: f64| ((fg * a + bg * (1. - a)) * 255.).round() as u8;
+    Some(Color::Rgb(channel(r, br), channel(g, bg), channel(b, bb)))
+}
+
+/// A filled area and its color, flattened onto what lies below it
+#[derive(Debug, Clone)]
+struct Backdrop {
+    bounds: [f64; 4],
+    color: Color,
+}
+
+/// The inherited properties the checks need
+#[derive(Debug, Clone)]
+struct Style {
+    /// `None` for `none` and paints without a single color, such as patterns and tokens
+    fill: Option<Color>,
+    font_size_px: f64,
+    is_bold: bool,
+    /// The start of the current text, from the `x` and `y` of `text` and `tspan`
+    position: [f64; 2],
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            fill: Some(Color::Black),
+            font_size_px: 16.,
+            is_bold: false,
+            position: [0., 0.],
+        }
+    }
+}
+
+impl Style {
+    fn inherit(&self, shape: &Shape) -> Self {
+        let fill = shape
+            .get_attribute("fill")
+            .map(|fill| fill.trim().parse::<Color>().ok());
+        let font_size_px = shape
+            .get_attribute("font-size")
+            .and_then(|size| parse_font_size(&size));
+        let is_bold = shape.get_attribute("font-weight").map(|weight| {
+            matches!(weight.trim(), "bold" | "bolder")
+                || weight
+                    .trim()
+                    .parse::<f64>()
+                    .is_ok_and(|weight| weight >= 700.)
+        });
+        let coordinate = |name: &str| match shape.tag_name() {
+            Some("text" | "tspan") => shape
+                .get_attribute(name)
+                .and_then(|value| parse_number(value.split([' ', ',']).next()?)),
+            _ => None,
+        };
+        Self {
+            fill: fill.unwrap_or_else(|| self.fill.clone()),
+            font_size_px: font_size_px.unwrap_or(self.font_size_px),
+            is_bold: is_bold.unwrap_or(self.is_bold),
+            position: [
+                coordinate("x").unwrap_or(self.position[0]),
+                coordinate("y").unwrap_or(self.position[1]),
+            ],
+        }
+    }
+}
+
+/// A plain or `px` number, as other units and percentages depend on the viewport
+fn parse_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    value
+        .strip_suffix("px")
+        .unwrap_or(value)
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn parse_font_size(size: &str) -> Option<f64> {
+    let size = size.trim();
+    if let Some(pt) = size.strip_suffix("pt") {
+        return pt.trim().parse::<f64>().ok().map(|pt| pt * 4. / 3.);
+    }
+    parse_number(size)
+}
@@ -0,0 +1,57 @@
+use crate::Shape;
+use crate::elements::ValidChildOf;
+use std::marker::PhantomData;
+
+/// Collects children for the `children` builder of the element `P`
+#[derive(Debug)]
+pub struct Children<P> {
+    children: Vec<Shape>,
+    parent: PhantomData<P>,
+}
+
+impl<P> Default for Children<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> Children<P> {
+    pub fn new() -> Self {
+        Self {
+            children: Vec::new(),
+            parent: PhantomData,
+        }
+    }
+
+    pub fn into_shapes(self) -> Vec<Shape> {
+        self.children
+    }
+
+    pub fn add<C: ValidChildOf<P>>(&mut self, child: C) -> &mut Self {
+        self.children.push(child.into());
+        self
+    }
+
+    pub fn add_if<C: ValidChildOf<P>>(&mut self, condition: bool, child: C) -> &mut Self {
+        if condition {
+            self.add(child);
+        }
+        self
+    }
+
+    pub fn add_opt<C: ValidChildOf<P>>(&mut self, child: Option<C>) -> &mut Self {
+        if let Some(child) = child {
+            self.add(child);
+        }
+        self
+    }
+
+    pub fn add_all<I>(&mut self, children: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: ValidChildOf<P>,
+    {
+        self.children.extend(children.into_iter().map(Into::into));
+        self
+    }
+}
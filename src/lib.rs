@@ -5,28 +5,51 @@ mod shape {
     include!(concat!(env!("OUT_DIR"), "/shape.rs"));
 }
 
+pub mod a11y;
+pub mod children;
+pub mod node;
+pub mod presets;
+pub mod recolor;
+pub mod serialize;
+pub mod theme;
 pub mod types;
 
+pub use easy_svg_macros::svg;
 pub use generated::*;
+pub use shape::Shape;
+
+// Lets `svg!` expand to `::easy_svg::...` inside this crate too
+extern crate self as easy_svg;
 #[cfg(test)]
 mod tests {
-    use crate::elements::{A, Circle, Line, Path, Rect, Svg, Text};
-    use crate::types::Color;
+    use crate::Shape;
+    use crate::a11y::{self, A11yIssue};
+    use crate::elements::{
+        A, Circle, Defs, Desc, Ellipse, ForeignObject, Line, Marker, Metadata, Path, Pattern,
+        Polygon, Polyline, Rect, ShapeElement, Style, Svg, Switch, Text, TextPath, Title, Tspan,
+    };
+    use crate::node::{GenericNode, SvgNode};
+    use crate::presets::{markers, patterns};
+    use crate::recolor;
+    use crate::serialize::SerializeOptions;
+    use crate::svg;
+    use crate::theme::Theme;
     use crate::types::PathData;
+    use crate::types::unions::{AutoOrLengthOrPercentage, LengthOrPercentage};
+    use crate::types::{
+        AriaRole, AttributeError, Attributes, BaseProfile, DashArray, FuncIri, LengthAdjust,
+        MarkerReference, Paint, Percentage, ReferrerPolicy, StrokeLinecap, StrokeLinejoin,
+        TextPathMethod, TextPathSide, VectorEffect, XHTML_NAMESPACE, Xhtml,
+    };
+    use crate::types::{Color, ColorFormat, ColorScale, ColorSpace, ParseColorError, Token};
 
     #[test]
     fn test_rect_and_circle() {
         let svg = Svg::new()
             .height(500.)
             .width(500.)
-            .add_child_shape_element(Circle::new().fill(Color::Aqua).r(20.).cx(15.).cy(30.))
-            .add_child_shape_element(
-                Rect::new()
-                    .width(200.)
-                    .height(400.)
-                    .x(20.)
-                    .fill(Color::DarkOliveGreen),
-            );
+            .add_child_shape_element(Circle::new(20.).fill(Color::Aqua).cx(15.).cy(30.))
+            .add_child_shape_element(Rect::new(200., 400.).x(20.).fill(Color::DarkOliveGreen));
 
         println!("{}", svg);
 
@@ -39,13 +62,9 @@ mod tests {
     #[test]
     fn test_a() {
         let svg = Svg::new().width(500.).height(500.).add_child_a(
-            A::new().href("https://google.com").add_child_rect(
-                Rect::new()
-                    .width(200.)
-                    .height(400.)
-                    .x(20.)
-                    .fill(Color::DarkOliveGreen),
-            ),
+            A::new()
+                .href("https://google.com")
+                .add_child_shape_element(Rect::new(200., 400.).x(20.).fill(Color::DarkOliveGreen)),
         );
         println!("{}", svg);
         assert_eq!(
@@ -59,13 +78,7 @@ mod tests {
         let svg = Svg::new()
             .width(500.)
             .height(500.)
-            .add_child_shape_element(
-                Rect::new()
-                    .width(200.)
-                    .height(400.)
-                    .x(20.)
-                    .fill(Color::DarkOliveGreen),
-            )
+            .add_child_shape_element(Rect::new(200., 400.).x(20.).fill(Color::DarkOliveGreen))
             .add_child_text(
                 Text::new()
                     .x(30.)
@@ -74,7 +87,7 @@ mod tests {
                     .add_child_string("Hello World".to_string())
                     .font_family("Arial".to_string()),
             )
-            .add_child_shape_element(Circle::new().fill(Color::DarkBlue).r(20.).cx(80.).cy(85.));
+            .add_child_shape_element(Circle::new(20.).fill(Color::DarkBlue).cx(80.).cy(85.));
 
         println!("{}", svg);
 
@@ -114,8 +127,7 @@ mod tests {
             .L(315., 10.);
 
         let svg = Svg::new().height(500.).width(500.).add_child_shape_element(
-            Path::new()
-                .d(path_data)
+            Path::new(path_data)
                 .stroke(Color::Black)
                 .fill(Color::Green)
                 .stroke_width(2.)
@@ -126,4 +138,1020 @@ mod tests {
             r#"<svg height="500" width="500"><path d="M 10 315 L 110 215 A 30 50 0 0 1 162.55 162.45 L 172.55 152.45 A 30 50 -45 0 1 215.1 109.9 L 315 10" fill="green" fill-opacity="0.5" stroke="black" stroke-width="2"/></svg>"#
         )
     }
+
+    #[test]
+    fn test_tspan_and_text_path() {
+        let svg = Svg::new().width(500.).height(500.).add_child_text(
+            Text::new()
+                .x(10.)
+                .y(20.)
+                .add_child_text_content_child_element(
+                    Tspan::new()
+                        .dx(vec![1., 2., 3.])
+                        .rotate([0., 15.])
+                        .font_size("12px".to_string())
+                        .add_child_string("Hi".to_string()),
+                )
+                .add_child_text_content_child_element(
+                    TextPath::new()
+                        .href("#curve")
+                        .start_offset(Percentage::from(50.))
+                        .method(TextPathMethod::Stretch)
+                        .add_child_string("along".to_string()),
+                ),
+        );
+
+        assert_eq!(
+            svg.to_string(),
+            r##"<svg height="500" width="500"><text x="10" y="20"><tspan dx="1 2 3" font-size="12px" rotate="0 15">Hi</tspan><textPath href="#curve" method="stretch" startOffset="50%">along</textPath></text></svg>"##
+        );
+    }
+
+    #[test]
+    fn test_markers() {
+        let svg = Svg::new()
+            .width(100.)
+            .height(100.)
+            .add_child_marker(markers::dot("dot", Color::Red))
+            .add_child_shape_element(
+                Polyline::new([(10., 10.), (50., 50.), (90., 10.)])
+                    .stroke(Color::Black)
+                    .marker_mid(MarkerReference::url("dot"))
+                    .marker_end(MarkerReference::None),
+            );
+
+        assert_eq!(
+            svg.to_string(),
+            r##"<svg height="100" width="100"><marker id="dot" markerHeight="4" markerUnits="strokeWidth" markerWidth="4" refX="5" refY="5" viewBox="0, 0, 10, 10"><circle cx="5" cy="5" fill="red" r="5"/></marker><polyline marker-end="none" marker-mid="url(#dot)" points="10,10 50,50 90,10" stroke="black"/></svg>"##
+        );
+    }
+
+    #[test]
+    fn test_pattern_fill() {
+        let svg = Svg::new()
+            .width(100.)
+            .height(100.)
+            .add_child_pattern(patterns::checkerboard("checks", Color::Black, 5.))
+            .add_child_shape_element(
+                Rect::new(100., 100.)
+                    .fill(Paint::url("checks"))
+                    .stroke(Color::Black),
+            );
+
+        assert_eq!(
+            svg.to_string(),
+            r##"<svg height="100" width="100"><pattern height="10" id="checks" patternUnits="userSpaceOnUse" width="10"><rect fill="black" height="5" width="5"/><rect fill="black" height="5" width="5" x="5" y="5"/></pattern><rect fill="url(#checks)" height="100" stroke="black" width="100"/></svg>"##
+        );
+
+        // lines cross the middle of the tile, so the tile edges don't clip half of each stroke
+        assert_eq!(
+            patterns::cross_hatching("grid", Color::Black, 8., 2., 45.).to_string(),
+            concat!(
+                r#"<pattern height="8" id="grid" patternTransform="rotate(45)" "#,
+                r#"patternUnits="userSpaceOnUse" width="8">"#,
+                r#"<path d="M 4 0 V 8" stroke="black" stroke-width="2"/>"#,
+                r#"<path d="M 0 4 H 8" stroke="black" stroke-width="2"/></pattern>"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_switch_and_foreign_object() {
+        let svg = Svg::new().width(200.).height(100.).add_child_switch(
+            Switch::new()
+                .add_child_foreign_object(
+                    ForeignObject::new(200., 100.)
+                        .required_extensions(XHTML_NAMESPACE)
+                        .add_child_xhtml(Xhtml::new("<p class=\"label\">Bonjour</p>")),
+                )
+                .add_child_text(
+                    Text::new()
+                        .system_language(["fr", "fr-CA"])
+                        .add_child_string("Bonjour".to_string()),
+                )
+                .add_child_text(Text::new().add_child_string("Hello".to_string())),
+        );
+
+        assert_eq!(
+            svg.to_string(),
+            r#"<svg height="100" width="200"><switch><foreignObject height="100" requiredExtensions="http://www.w3.org/1999/xhtml" width="200"><p xmlns="http://www.w3.org/1999/xhtml" class="label">Bonjour</p></foreignObject><text systemLanguage="fr, fr-CA">Bonjour</text><text>Hello</text></switch></svg>"#
+        );
+
+        // every top level element is namespaced, unless it declares a default namespace
+        let xhtml = Xhtml::new(concat!(
+            r#"<p title="a > b, xmlns=none">a<br></p><!-- <i> --><p>b</p>"#,
+            r#"<span xmlns:x="urn:x"/><div xmlns="http://www.w3.org/1999/xhtml"/>"#
+        ));
+        assert_eq!(
+            xhtml.to_string(),
+            concat!(
+                r#"<p xmlns="http://www.w3.org/1999/xhtml" title="a > b, xmlns=none">a<br></p>"#,
+                r#"<!-- <i> --><p xmlns="http://www.w3.org/1999/xhtml">b</p>"#,
+                r#"<span xmlns="http://www.w3.org/1999/xhtml" xmlns:x="urn:x"/>"#,
+                r#"<div xmlns="http://www.w3.org/1999/xhtml"/>"#
+            )
+        );
+        assert_eq!(Xhtml::new("plain text").to_string(), "plain text");
+    }
+
+    #[test]
+    fn test_a11y_check() {
+        let unnamed = Svg::new()
+            .width(100.)
+            .height(100.)
+            .add_child_shape_element(Circle::new(10.).role(AriaRole::Img).id("dot"))
+            .add_child_text(
+                Text::new()
+                    .x(20.)
+                    .y(50.)
+                    .fill(Color::LightGray)
+                    .add_child_string("faint".to_string()),
+            );
+        let issues = a11y::check(&unnamed.into());
+        assert_eq!(issues.len(), 3);
+        assert!(matches!(
+            &issues[0],
+            A11yIssue::MissingAccessibleName { tag_name, .. } if tag_name == "svg"
+        ));
+        assert_eq!(
+            issues[1],
+            A11yIssue::MissingAccessibleName {
+                tag_name: "circle".to_string(),
+                id: Some("dot".to_string())
+            }
+        );
+        assert!(matches!(
+            &issues[2],
+            A11yIssue::InsufficientContrast { tag_name, ratio, .. } if tag_name == "text" && *ratio < 1.5
+        ));
+
+        // gray on white passes only as large text, which is 24px or 14pt bold
+        let gray_text = |size: &str| {
+            Text::new()
+                .fill(Color::Gray)
+                .font_size(size.to_string())
+                .add_child_string("label".to_string())
+        };
+        let named = Svg::new()
+            .width(100.)
+            .height(100.)
+            .role(AriaRole::GraphicsDocument)
+            .add_child_descriptive_element(Title::new().add_child_string("Chart".to_string()))
+            .add_child_text(gray_text("32px"))
+            .add_child_text(gray_text("14pt").attr("font-weight", "bold"))
+            .add_child_text(gray_text("14pt").id("small"))
+            .add_child_text(
+                Text::new()
+                    .fill(Color::LightGray)
+                    .aria_hidden(true)
+                    .add_child_string("decorative".to_string()),
+            );
+        assert_eq!(
+            a11y::check(&named.into()),
+            vec![A11yIssue::InsufficientContrast {
+                tag_name: "text".to_string(),
+                id: Some("small".to_string()),
+                ratio: Color::Gray.contrast_ratio(&Color::White).unwrap(),
+                required: a11y::MIN_CONTRAST,
+            }]
+        );
+        assert!(Color::Black.contrast_ratio(&Color::White).unwrap() > 20.9);
+
+        // white labels are checked against the bar under them, not the page
+        let bars = Svg::new()
+            .width(100.)
+            .height(100.)
+            .aria_label("Sales")
+            .add_child_shape_element(Rect::new(20., 60.).y(40.).fill(Color::Navy))
+            .add_child_text(
+                Text::new()
+                    .x(5.)
+                    .y(90.)
+                    .fill(Color::White)
+                    .add_child_string("12".to_string()),
+            )
+            .add_child_text(
+                Text::new()
+                    .x(50.)
+                    .y(90.)
+                    .fill(Color::White)
+                    .id("outside")
+                    .add_child_string("8".to_string()),
+            );
+        let issues = a11y::check(&bars.into());
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(
+            &issues[0],
+            A11yIssue::InsufficientContrast { id: Some(id), .. } if id == "outside"
+        ));
+
+        // labels are free text, so they are escaped like any attribute value
+        assert_eq!(
+            Rect::new(1., 1.)
+                .aria_label(r#"Sales "Q1" <draft> & more"#)
+                .to_string(),
+            r#"<rect aria-label="Sales &quot;Q1&quot; &lt;draft&gt; &amp; more" height="1" width="1"/>"#
+        );
+    }
+
+    #[test]
+    fn test_extra_attributes() {
+        let rect = Rect::new(10., 5.)
+            .data("series", 2)
+            .attr("inkscape:label", "a \"quoted\" <label>")
+            .data("series", 3);
+
+        assert_eq!(
+            rect.to_string(),
+            r#"<rect height="5" width="10" data-series="3" inkscape:label="a &quot;quoted&quot; &lt;label&gt;"/>"#
+        );
+        assert_eq!(rect.get_attribute("data-series").as_deref(), Some("3"));
+
+        let yaml = serde_yaml::to_string(&rect).unwrap();
+        let round_trip: Rect = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(round_trip.to_string(), rect.to_string());
+
+        assert_eq!(
+            Rect::new(1., 1.).try_attr("fill", "red").unwrap_err(),
+            AttributeError::Collision("fill".to_string())
+        );
+        assert_eq!(
+            Rect::new(1., 1.).try_data("rowIndex", 1).unwrap_err(),
+            AttributeError::InvalidName("data-rowIndex".to_string())
+        );
+        assert!(Rect::new(1., 1.).try_attr("bad name", 1).is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_and_experimental_attributes() {
+        let svg = Svg::new()
+            .width(10.)
+            .height(10.)
+            .base_profile(BaseProfile::Full);
+        assert_eq!(
+            svg.to_string(),
+            r#"<svg baseProfile="full" height="10" width="10"/>"#
+        );
+
+        assert_eq!(
+            Rect::ATTRIBUTES.contains(&"elementtiming"),
+            cfg!(feature = "experimental")
+        );
+    }
+
+    #[test]
+    fn test_idl_properties_map_to_content_attributes() {
+        let circle = Circle::new(1.).class_name("dot").tab_index(0);
+        assert_eq!(
+            circle.to_string(),
+            r#"<circle class="dot" r="1" tabindex="0"/>"#
+        );
+        assert_eq!(circle.get_attribute("class").as_deref(), Some("dot"));
+        assert!(Circle::ATTRIBUTES.contains(&"tabindex"));
+        assert!(!Circle::ATTRIBUTES.contains(&"innerHtml"));
+    }
+
+    #[test]
+    fn test_keyword_enums() {
+        assert_eq!(
+            LengthAdjust::SpacingAndGlyphs.to_string(),
+            "spacingAndGlyphs"
+        );
+        assert_eq!(
+            "no-referrer-when-downgrade".parse::<ReferrerPolicy>(),
+            Ok(ReferrerPolicy::NoReferrerWhenDowngrade)
+        );
+        assert_eq!(LengthAdjust::default(), LengthAdjust::Spacing);
+
+        let error = "sideways".parse::<TextPathSide>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`sideways` is not one of `left`, `right`"
+        );
+
+        let text = Text::new().length_adjust(LengthAdjust::SpacingAndGlyphs);
+        assert_eq!(
+            text.to_string(),
+            r#"<text lengthAdjust="spacingAndGlyphs"/>"#
+        );
+    }
+
+    #[test]
+    fn test_effective_values_and_omit_defaults() {
+        let circle = Circle::new(5.).cx(0.).stroke_width(1.).fill(Color::Red);
+        assert_eq!(circle.effective_cx(), LengthOrPercentage::from(0.));
+        assert_eq!(circle.effective_stroke_width(None), 1.);
+        assert_eq!(circle.effective_stroke(None), Paint::None);
+        assert_eq!(
+            circle.effective_stroke(Some(Paint::Color(Color::Blue))),
+            Paint::Color(Color::Blue)
+        );
+        assert_eq!(circle.effective_fill(None), Paint::Color(Color::Red));
+        assert_eq!(Text::new().effective_length_adjust(), LengthAdjust::Spacing);
+
+        assert_eq!(
+            circle.to_string(),
+            r#"<circle cx="0" fill="red" r="5" stroke-width="1"/>"#
+        );
+        let options = SerializeOptions::new().omit_defaults(true);
+        assert_eq!(
+            circle.to_svg_string(&options),
+            r#"<circle fill="red" r="5" stroke-width="1"/>"#
+        );
+
+        // the black fill overrides the inherited blue one, so it is kept
+        let svg = Svg::new()
+            .width(1.)
+            .height(1.)
+            .attr("fill", "blue")
+            .add_child(Rect::new(1., 1.).fill(Color::Black));
+        assert_eq!(
+            svg.to_svg_string(&options),
+            r#"<svg height="1" width="1" fill="blue"><rect fill="black" height="1" width="1"/></svg>"#
+        );
+    }
+
+    #[test]
+    fn test_required_attributes() {
+        let circle = Circle::new(0.).cx(0.).r(4.);
+        assert_eq!(circle.r, LengthOrPercentage::from(4.));
+        assert_eq!(circle.get_attribute("r").as_deref(), Some("4"));
+        let options = SerializeOptions::new().omit_defaults(true);
+        assert_eq!(circle.to_svg_string(&options), r#"<circle r="4"/>"#);
+
+        let error = serde_yaml::from_str::<Circle>("children: []\n").unwrap_err();
+        assert!(error.to_string().contains("missing field `r`"), "{}", error);
+        let round_trip: Circle =
+            serde_yaml::from_str(&serde_yaml::to_string(&circle).unwrap()).unwrap();
+        assert_eq!(round_trip.to_string(), circle.to_string());
+
+        // an svg scales to its container without a size, and an ellipse's ry defaults to rx
+        let svg = Svg::new().view_box((0., 0., 10., 10.)).add_child(
+            Svg::new()
+                .width(Percentage::from(50.))
+                .add_child(Ellipse::new(2.)),
+        );
+        assert_eq!(
+            svg.to_string(),
+            r#"<svg viewBox="0, 0, 10, 10"><svg width="50%"><ellipse rx="2"/></svg></svg>"#
+        );
+        assert_eq!(
+            Ellipse::new(2.).effective_ry(),
+            AutoOrLengthOrPercentage::Auto
+        );
+    }
+
+    #[test]
+    fn test_color_parsing() {
+        let parse = |color: &str| color.parse::<Color>();
+        assert_eq!(parse("DarkOliveGreen"), Ok(Color::DarkOliveGreen));
+        assert_eq!(parse(" REBECCAPURPLE "), Ok(Color::RebeccaPurple));
+        assert_eq!(parse("currentcolor"), Ok(Color::CurrentColor));
+        assert_eq!(parse("#0f0a"), Ok(Color::Hex("#0f0a".to_string())));
+        assert_eq!(parse("rgb(255, 0, 128)"), Ok(Color::Rgb(255, 0, 128)));
+        assert_eq!(parse("rgb(100% 0% 50%)"), Ok(Color::Rgb(255, 0, 128)));
+        assert_eq!(parse("rgba(1, 2, 3, 50%)"), Ok(Color::Rgba(1, 2, 3, 0.5)));
+        assert_eq!(parse("rgb(1 2 3 / 0.25)"), Ok(Color::Rgba(1, 2, 3, 0.25)));
+        assert_eq!(
+            parse("hsl(-0.25turn, 50%, 40%)"),
+            Ok(Color::Hsl(270, 50, 40))
+        );
+        assert_eq!(
+            parse("HSLA(120deg 100 25 / .5)"),
+            Ok(Color::Hsla(120, 100, 25, 0.5))
+        );
+
+        for color in [
+            Color::AliceBlue,
+            Color::YellowGreen,
+            Color::Transparent,
+            Color::CurrentColor,
+            Color::Rgb(1, 2, 3),
+            Color::Rgba(1, 2, 3, 0.4),
+            Color::Hsl(200, 10, 20),
+            Color::Hsla(200, 10, 20, 0.75),
+            Color::Hex("#A1B2C3".to_string()),
+        ] {
+            assert_eq!(parse(&color.to_string()), Ok(color));
+        }
+
+        assert_eq!(
+            parse("#12345"),
+            Err(ParseColorError::InvalidHex("#12345".to_string()))
+        );
+        assert_eq!(
+            parse("rgb(1, 2)"),
+            Err(ParseColorError::InvalidFunction("rgb(1, 2)".to_string()))
+        );
+        assert_eq!(
+            parse("var(--primary)"),
+            Err(ParseColorError::Unknown("var(--primary)".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_color_math() {
+        assert_eq!(Color::Red.to_rgba(), Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(
+            Color::RebeccaPurple.to_hsla(),
+            Some(Color::Hsl(270, 50, 40))
+        );
+        assert_eq!(
+            Color::Navy.to_format(ColorFormat::Hex),
+            Some(Color::Hex("#000080".to_string()))
+        );
+        assert_eq!(Color::CurrentColor.to_rgba(), None);
+
+        assert_eq!(
+            Color::Hsl(0, 100, 50).lighten(0.2),
+            Some(Color::Hsl(0, 100, 70))
+        );
+        assert_eq!(
+            Color::Hsl(0, 100, 50).darken(0.6),
+            Some(Color::Hsl(0, 100, 0))
+        );
+        // HSL colors keep their hue as a gray and don't drift when changed back and forth
+        let gray = Color::Hsl(120, 50, 50).desaturate(0.5).unwrap();
+        assert_eq!(gray, Color::Hsl(120, 0, 50));
+        assert_eq!(gray.saturate(0.5), Some(Color::Hsl(120, 50, 50)));
+        let mut color = Color::Hsla(200, 40, 30, 0.5);
+        for _ in 0..10 {
+            color = color.lighten(0.07).unwrap().darken(0.07).unwrap();
+        }
+        assert_eq!(color, Color::Hsla(200, 40, 30, 0.5));
+        assert_eq!(
+            Color::Hsl(350, 50, 50).rotate_hue(20.),
+            Some(Color::Hsl(10, 50, 50))
+        );
+        assert_eq!(
+            Color::Hex("#336699".to_string()).invert(),
+            Some(Color::Hex("#cc9966".to_string()))
+        );
+        assert_eq!(
+            Color::Black.mix(&Color::White, 0.5),
+            Some(Color::Rgb(128, 128, 128))
+        );
+        assert_eq!(
+            Color::Blue.with_alpha(0.5),
+            Some(Color::Rgba(0, 0, 255, 0.5))
+        );
+        assert_eq!(
+            Color::Hex("#000".to_string()).with_alpha(0.),
+            Some(Color::Hex("#00000000".to_string()))
+        );
+        assert_eq!(
+            Color::from_srgba([1., 1., 1., 1.], ColorFormat::Hsl).relative_luminance(),
+            Some(1.)
+        );
+    }
+
+    #[test]
+    fn test_color_spaces() {
+        assert_eq!(
+            "oklch(70% 0.15 180deg)".parse(),
+            Ok(Color::Oklch(0.7, 0.15, 180., 1.))
+        );
+        assert_eq!(
+            "lab(50% 40 -20 / 0.5)".parse(),
+            Ok(Color::Lab(50., 40., -20., 0.5))
+        );
+        assert_eq!(
+            Color::Lab(50., 40., -20., 0.5).to_string(),
+            "lab(50 40 -20 / 0.5)"
+        );
+        assert_eq!(
+            "hwb(120 0% 0%)".parse::<Color>().unwrap().to_rgba(),
+            Some(Color::Rgb(0, 255, 0))
+        );
+        assert_eq!(
+            Color::Red.to_format(ColorFormat::Oklch),
+            Some(Color::Oklch(0.628, 0.2577, 29.23, 1.))
+        );
+        assert_eq!(
+            Color::White.to_format(ColorFormat::Lab),
+            Some(Color::Lab(100., 0., 0., 1.))
+        );
+
+        // out of sRGB, so the chroma is reduced at the same Oklch lightness, which is higher
+        // than that of sRGB red
+        let p3_red: Color = "color(display-p3 1 0 0)".parse().unwrap();
+        assert_eq!(p3_red.to_rgba(), Some(Color::Rgb(255, 11, 12)));
+        assert_eq!(
+            Color::Oklch(0.7, 0.4, 150., 1.).to_rgba(),
+            Some(Color::Rgb(0, 194, 72))
+        );
+
+        let circle = Circle::new(5.).fill(p3_red);
+        assert_eq!(
+            circle.to_svg_string(&SerializeOptions::new()),
+            r#"<circle fill="color(display-p3 1 0 0)" r="5"/>"#
+        );
+        assert_eq!(
+            circle.to_svg_string(&SerializeOptions::new().srgb_fallback(true)),
+            r#"<circle fill="rgb(255, 11, 12)" r="5"/>"#
+        );
+    }
+
+    #[test]
+    fn test_color_scales() {
+        let hex = |hex: &str| Color::Hex(hex.to_string());
+
+        let viridis = ColorScale::viridis().domain(0., 50.);
+        assert_eq!(viridis.sample(25.), hex("#21918c"));
+        assert_eq!(viridis.sample(-10.), hex("#440154"));
+        assert_eq!(viridis.sample(2.5), hex("#461365"));
+        assert_eq!(
+            ColorScale::magma().colors(3),
+            vec![hex("#000004"), hex("#b73779"), hex("#fcfdbf")]
+        );
+
+        let palette = ColorScale::tableau10().colors(12);
+        assert_eq!(palette[1], hex("#f28e2c"));
+        assert_eq!(palette[11], palette[1]);
+        assert_eq!(ColorScale::set1().sample(0.99), hex("#999999"));
+
+        // centered on zero even though the domain is not symmetric
+        let diverging = ColorScale::diverging(Color::Red, Color::White, Color::Blue)
+            .domain(-10., 30.)
+            .midpoint(0.);
+        assert_eq!(diverging.sample(0.), hex("#ffffff"));
+        assert_eq!(diverging.sample(-10.), hex("#ff0000"));
+        assert_eq!(diverging.sample(30.), hex("#0000ff"));
+
+        let scale = ColorScale::new([Color::Black, Color::White]).format(ColorFormat::Rgb);
+        assert_eq!(
+            scale.clone().interpolation(ColorSpace::Srgb).sample(0.5),
+            Color::Rgb(128, 128, 128)
+        );
+        assert_eq!(scale.sample(0.5), Color::Rgb(99, 99, 99));
+        // through magenta, the shorter way around the hue circle
+        assert_eq!(
+            ColorScale::new([Color::Red, Color::Blue])
+                .interpolation(ColorSpace::Oklch)
+                .format(ColorFormat::Rgb)
+                .sample(0.5),
+            Color::Rgb(183, 0, 190)
+        );
+        assert_eq!(
+            ColorScale::new(Vec::<Color>::new()).sample(0.5),
+            Color::Transparent
+        );
+    }
+
+    #[test]
+    fn test_recolor() {
+        let mut document: Shape = Svg::new()
+            .width(10.)
+            .height(10.)
+            .add_child_shape_element(
+                Circle::new(5.)
+                    .fill(Color::Red)
+                    .stroke(Paint::Url(FuncIri::new("grid"), Some(Color::Blue))),
+            )
+            .add_child_shape_element(Rect::new(5., 5.).style("fill: #00ff00; opacity: 0.5"))
+            .add_child_shape_element(Rect::new(5., 5.).fill(Color::CurrentColor))
+            .into();
+
+        document.map_colors(&mut recolor::grayscale);
+        assert_eq!(
+            document.to_string(),
+            concat!(
+                r#"<svg height="10" width="10">"#,
+                r##"<circle fill="rgb(127, 127, 127)" r="5" stroke="url(#grid) rgb(76, 76, 76)"/>"##,
+                r#"<rect height="5" style="fill: #dcdcdc; opacity: 0.5" width="5"/>"#,
+                r#"<rect fill="currentColor" height="5" width="5"/>"#,
+                "</svg>"
+            )
+        );
+
+        let mut gradient = Shape::from(
+            GenericNode::new("linearGradient")
+                .add_child(GenericNode::new("stop").attr("stop-color", "yellow")),
+        );
+        gradient.map_colors(&mut |color: &Color| color.invert().unwrap());
+        assert_eq!(
+            gradient.to_string(),
+            r#"<linearGradient><stop stop-color="rgb(0, 0, 255)"/></linearGradient>"#
+        );
+
+        let theme = Theme::new()
+            .color(Token::Primary, Color::Hex("#3366ff".to_string()))
+            .dark_color(Token::Primary, Color::Hex("#99bbff".to_string()))
+            .color(
+                Token::Custom("brand".to_string()),
+                Color::Token(Token::Primary),
+            )
+            .font(Token::BodyFont, "Inter, sans-serif");
+        let mut themed: Shape = Svg::new()
+            .add_child(theme.style_element(&SerializeOptions::new()))
+            .add_child(
+                Style::new().add_child(
+                    concat!(
+                        ".label, a:hover { fill: red; font: 12px \"a;b\" } ",
+                        "@media print { /* ink; */ text { stroke: #0000ff !important; } }"
+                    )
+                    .to_string(),
+                ),
+            )
+            .into();
+        themed.map_colors(&mut recolor::grayscale);
+        assert_eq!(
+            themed.to_string(),
+            concat!(
+                r#"<svg><style><![CDATA["#,
+                ":root { --primary: #747474; --brand: var(--primary); ",
+                "--body-font: Inter, sans-serif; } ",
+                "@media (prefers-color-scheme: dark) { :root { --primary: #bbbbbb; } }",
+                r#"]]></style><style>.label, a:hover { fill: rgb(127, 127, 127); font: 12px "a;b" } "#,
+                "@media print { /* ink; */ text { stroke: #4c4c4c !important; } }",
+                "</style></svg>"
+            )
+        );
+
+        assert_eq!(recolor::dark_mode(&Color::White), Color::Rgb(0, 0, 0));
+        assert_eq!(
+            recolor::dark_mode(&Color::Hex("#000".to_string())),
+            Color::Hex("#ffffff".to_string())
+        );
+        assert_eq!(recolor::protanopia(&Color::Red), Color::Rgb(109, 95, 0));
+        assert_eq!(recolor::deuteranopia(&Color::Red), Color::Rgb(163, 144, 0));
+        assert_eq!(recolor::tritanopia(&Color::Blue), Color::Rgb(0, 107, 150));
+        assert_eq!(recolor::dark_mode(&Color::Transparent), Color::Transparent);
+    }
+
+    #[test]
+    fn test_theme_tokens() {
+        let theme = Theme::new()
+            .color(Token::Primary, Color::Hex("#3366ff".to_string()))
+            .dark_color(Token::Primary, Color::Hex("#99bbff".to_string()))
+            .color(Token::Surface, Color::White)
+            .color(
+                Token::Custom("brand".to_string()),
+                Color::Token(Token::Primary),
+            )
+            .font(Token::BodyFont, "Inter, sans-serif");
+        let text = Text::new()
+            .fill(Color::Token(Token::Primary))
+            .stroke(Color::Token(Token::Custom("brand".to_string())))
+            .font_family(Token::BodyFont)
+            .add_child("Hi".to_string());
+
+        assert_eq!(
+            text.to_string(),
+            r#"<text fill="var(--primary)" font-family="var(--body-font)" stroke="var(--brand)">Hi</text>"#
+        );
+        assert_eq!(
+            text.to_svg_string(&SerializeOptions::new().theme(theme.clone())),
+            r##"<text fill="#3366ff" font-family="Inter, sans-serif" stroke="#3366ff">Hi</text>"##
+        );
+        assert_eq!(
+            text.to_svg_string(&SerializeOptions::new().theme(theme.to_dark())),
+            r##"<text fill="#99bbff" font-family="Inter, sans-serif" stroke="#99bbff">Hi</text>"##
+        );
+        let cyclic = Theme::new()
+            .color(Token::Accent, Color::Token(Token::Danger))
+            .color(Token::Danger, Color::Token(Token::Accent));
+        assert_eq!(cyclic.resolve_color(&Token::Accent), None);
+        assert_eq!(
+            Rect::new(1., 1.)
+                .fill(Color::Token(Token::Accent))
+                .to_svg_string(&SerializeOptions::new().theme(cyclic)),
+            r#"<rect fill="var(--accent)" height="1" width="1"/>"#
+        );
+
+        let fonts = Theme::new()
+            .font(Token::BodyFont, "Inter, sans-serif")
+            .font(Token::HeadingFont, Token::BodyFont)
+            .font(Token::MonospaceFont, Token::Custom("code".to_string()))
+            .font(Token::Custom("code".to_string()), Token::MonospaceFont);
+        assert_eq!(
+            fonts.resolve_font(&Token::HeadingFont),
+            Some("Inter, sans-serif")
+        );
+        assert_eq!(fonts.resolve_font(&Token::MonospaceFont), None);
+        let options = SerializeOptions::new().theme(fonts);
+        assert_eq!(
+            Text::new()
+                .font_family(Token::HeadingFont)
+                .to_svg_string(&options),
+            r#"<text font-family="Inter, sans-serif"/>"#
+        );
+        assert_eq!(
+            Text::new()
+                .font_family(Token::MonospaceFont)
+                .to_svg_string(&options),
+            r#"<text font-family="var(--monospace-font)"/>"#
+        );
+
+        let svg = Svg::new()
+            .width(10.)
+            .height(10.)
+            .add_child(theme.style_element(&SerializeOptions::new()))
+            .add_child(Rect::new(10., 10.).fill(Color::Token(Token::Surface)));
+        assert_eq!(
+            svg.to_string(),
+            concat!(
+                r#"<svg height="10" width="10"><style><![CDATA["#,
+                ":root { --primary: #3366ff; --surface: white; --brand: var(--primary); ",
+                "--body-font: Inter, sans-serif; } ",
+                "@media (prefers-color-scheme: dark) { :root { --primary: #99bbff; } }",
+                r#"]]></style><rect fill="var(--surface)" height="10" width="10"/></svg>"#
+            )
+        );
+
+        // a value can't end the CDATA section early
+        let hostile = Theme::new().font(Token::BodyFont, "a]]><script/>");
+        assert_eq!(
+            hostile.style_element(&SerializeOptions::new()).to_string(),
+            "<style><![CDATA[:root { --body-font: a]]]]><![CDATA[><script/>; }]]></style>"
+        );
+
+        // tokens survive a round trip, and so do family names, even those spelled like a token
+        for text in [
+            Text::new().font_family(Token::BodyFont),
+            Text::new().font_family(Token::Custom("display".to_string())),
+            Text::new().font_family("Arial"),
+            Text::new().font_family("Text"),
+        ] {
+            let yaml = serde_yaml::to_string(&text).unwrap();
+            let deserialized: Text = serde_yaml::from_str(&yaml).unwrap();
+            assert_eq!(deserialized.to_string(), text.to_string());
+        }
+    }
+
+    #[test]
+    fn test_stroke_styling() {
+        let line = Line::new()
+            .stroke(Color::Black)
+            .stroke_width(2.)
+            .stroke_dasharray([5, 3])
+            .stroke_dashoffset(Percentage::from(10.))
+            .stroke_linecap(StrokeLinecap::Round)
+            .stroke_linejoin(StrokeLinejoin::MiterClip)
+            .stroke_miterlimit(8.)
+            .stroke_opacity(0.5)
+            .vector_effect(VectorEffect::NonScalingStroke);
+        assert_eq!(
+            line.to_string(),
+            concat!(
+                r#"<line stroke="black" stroke-dasharray="5 3" stroke-dashoffset="10%" "#,
+                r#"stroke-linecap="round" stroke-linejoin="miter-clip" stroke-miterlimit="8" "#,
+                r#"stroke-opacity="0.5" stroke-width="2" vector-effect="non-scaling-stroke"/>"#
+            )
+        );
+
+        // inherited fill and stroke properties override those of the parent, so they are kept
+        let ellipse = Ellipse::new(1.)
+            .ry(2.)
+            .fill_opacity(1.)
+            .stroke_dasharray(DashArray::None)
+            .stroke_linecap(StrokeLinecap::Butt)
+            .stroke_miterlimit(4.)
+            .vector_effect(VectorEffect::None);
+        assert_eq!(
+            ellipse.to_svg_string(&SerializeOptions::new().omit_defaults(true)),
+            concat!(
+                r#"<ellipse fill-opacity="1" rx="1" ry="2" stroke-dasharray="none" "#,
+                r#"stroke-linecap="butt" stroke-miterlimit="4"/>"#
+            )
+        );
+        assert_eq!(Ellipse::new(1.).effective_fill_opacity(Some(0.5)), 0.5);
+        assert_eq!(
+            Line::new()
+                .stroke_dasharray(Vec::<f64>::new())
+                .get_attribute("stroke-dasharray")
+                .as_deref(),
+            Some("none")
+        );
+        let polygon = Polygon::new([(0., 0.)]);
+        assert_eq!(polygon.effective_stroke_miterlimit(None), 4.);
+        assert_eq!(
+            polygon.effective_stroke_linecap(Some(StrokeLinecap::Round)),
+            StrokeLinecap::Round
+        );
+        assert_eq!("miter-clip".parse(), Ok(StrokeLinejoin::MiterClip));
+        assert!("dotted".parse::<StrokeLinecap>().is_err());
+    }
+
+    #[derive(Debug, Clone)]
+    struct Gauge {
+        value: f64,
+    }
+
+    impl SvgNode for Gauge {
+        fn tag_name(&self) -> &str {
+            "x-gauge"
+        }
+
+        fn attributes(&self) -> Attributes {
+            let mut attributes = Attributes::new();
+            attributes
+                .try_insert("value".to_string(), self.value.to_string(), &[])
+                .unwrap();
+            attributes
+        }
+    }
+
+    impl ShapeElement for Gauge {}
+
+    impl From<Gauge> for Shape {
+        fn from(gauge: Gauge) -> Self {
+            Shape::custom(gauge)
+        }
+    }
+
+    #[test]
+    fn test_custom_nodes() {
+        let svg = Svg::new()
+            .view_box((0., 0., 10., 10.))
+            .add_child_shape_element(Gauge { value: 0.5 });
+        assert_eq!(
+            svg.to_string(),
+            r#"<svg viewBox="0, 0, 10, 10"><x-gauge value="0.5"/></svg>"#
+        );
+
+        let gauge = &svg.child_nodes()[0];
+        assert_eq!(gauge.tag_name(), Some("x-gauge"));
+        assert_eq!(gauge.get_attribute("value").as_deref(), Some("0.5"));
+
+        let shape = Shape::from(GenericNode::new("x-group").attr("id", "g").add_child(svg));
+        let yaml = serde_yaml::to_string(&shape).unwrap();
+        let deserialized: Shape = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(
+            deserialized.to_string(),
+            r#"<x-group id="g"><svg viewBox="0, 0, 10, 10"><x-gauge value="0.5"/></svg></x-group>"#
+        );
+    }
+
+    #[test]
+    fn test_generic_add_child() {
+        let svg = Svg::new()
+            .width(10.)
+            .height(10.)
+            .add_child(Rect::new(1., 1.))
+            .add_child(Text::new().add_child("label".to_string()))
+            .add_child(Switch::new().add_child(Circle::new(1.)));
+        assert_eq!(
+            svg.to_string(),
+            r#"<svg height="10" width="10"><rect height="1" width="1"/><text>label</text><switch><circle r="1"/></switch></svg>"#
+        );
+
+        assert!(Svg::ALLOWED_CHILDREN.contains(&"rect"));
+        assert!(Text::ALLOWED_CHILDREN.contains(&"tspan"));
+        assert!(!Title::ALLOWED_CHILDREN.contains(&"rect"));
+    }
+
+    #[test]
+    fn test_bulk_and_conditional_children() {
+        let values = [1., 2., 3.];
+        let mut svg = Svg::new()
+            .width(10.)
+            .height(10.)
+            .add_children(values.iter().map(|value| Circle::new(*value)))
+            .add_child_if(false, Rect::new(1., 1.))
+            .add_child_opt(Some(Rect::new(4., 4.)))
+            .add_child_opt(None::<Rect>)
+            .children(|children| {
+                for value in values {
+                    children.add_if(value > 2., Rect::new(value, value));
+                }
+            });
+        svg.extend([Circle::new(5.)]);
+        assert_eq!(
+            svg.to_string(),
+            r#"<svg height="10" width="10"><circle r="1"/><circle r="2"/><circle r="3"/><rect height="4" width="4"/><rect height="3" width="3"/><circle r="5"/></svg>"#
+        );
+    }
+
+    #[test]
+    fn test_content_model_matches_spec() {
+        let descriptive = ["desc", "metadata", "title"];
+        let shapes = [
+            "circle", "ellipse", "line", "path", "polygon", "polyline", "rect",
+        ];
+        let container = [
+            &descriptive[..],
+            &shapes,
+            &[
+                "a",
+                "defs",
+                "foreignObject",
+                "marker",
+                "pattern",
+                "style",
+                "svg",
+                "switch",
+                "text",
+            ],
+        ]
+        .concat();
+        let expected: [(&str, &[&str], Vec<&str>); 21] = [
+            ("a", A::ALLOWED_CHILDREN, {
+                let mut children = container.clone();
+                children.retain(|child| *child != "a");
+                children.extend(["textPath", "tspan"]);
+                children
+            }),
+            ("circle", Circle::ALLOWED_CHILDREN, descriptive.to_vec()),
+            ("defs", Defs::ALLOWED_CHILDREN, container.clone()),
+            ("desc", Desc::ALLOWED_CHILDREN, {
+                let mut children = container.clone();
+                children.extend(["textPath", "tspan"]);
+                children
+            }),
+            ("ellipse", Ellipse::ALLOWED_CHILDREN, descriptive.to_vec()),
+            (
+                "foreignObject",
+                ForeignObject::ALLOWED_CHILDREN,
+                descriptive.to_vec(),
+            ),
+            ("line", Line::ALLOWED_CHILDREN, descriptive.to_vec()),
+            ("marker", Marker::ALLOWED_CHILDREN, container.clone()),
+            ("metadata", Metadata::ALLOWED_CHILDREN, vec![]),
+            ("path", Path::ALLOWED_CHILDREN, descriptive.to_vec()),
+            ("pattern", Pattern::ALLOWED_CHILDREN, container.clone()),
+            ("polygon", Polygon::ALLOWED_CHILDREN, descriptive.to_vec()),
+            ("polyline", Polyline::ALLOWED_CHILDREN, descriptive.to_vec()),
+            ("rect", Rect::ALLOWED_CHILDREN, descriptive.to_vec()),
+            ("style", Style::ALLOWED_CHILDREN, vec![]),
+            ("svg", Svg::ALLOWED_CHILDREN, container.clone()),
+            ("switch", Switch::ALLOWED_CHILDREN, {
+                let mut children = [&descriptive[..], &shapes].concat();
+                children.extend(["a", "foreignObject", "svg", "switch", "text"]);
+                children
+            }),
+            ("text", Text::ALLOWED_CHILDREN, {
+                let mut children = descriptive.to_vec();
+                children.extend(["a", "textPath", "tspan"]);
+                children
+            }),
+            ("textPath", TextPath::ALLOWED_CHILDREN, {
+                let mut children = descriptive.to_vec();
+                children.extend(["a", "tspan"]);
+                children
+            }),
+            ("title", Title::ALLOWED_CHILDREN, vec![]),
+            ("tspan", Tspan::ALLOWED_CHILDREN, {
+                let mut children = descriptive.to_vec();
+                children.extend(["a", "tspan"]);
+                children
+            }),
+        ];
+        for (parent, allowed, mut expected) in expected {
+            let mut allowed = allowed.to_vec();
+            allowed.sort_unstable();
+            expected.sort_unstable();
+            assert_eq!(allowed, expected, "children of <{}>", parent);
+        }
+
+        let svg = Svg::new()
+            .width(10.)
+            .height(10.)
+            .add_child_shape_element(Ellipse::new(1.).ry(2.))
+            .add_child_structural_element(Defs::new());
+        assert_eq!(
+            svg.to_string(),
+            r#"<svg height="10" width="10"><ellipse rx="1" ry="2"/><defs/></svg>"#
+        );
+    }
+
+    #[test]
+    fn test_svg_macro() {
+        let radii = [10., 20.];
+        let legend = Text::new().x(5.).add_child("legend".to_string());
+        let show_border = true;
+        let svg = svg! {
+            <svg width=500 height=500>
+                <rect x=20 width=200 height=400 fill=Color::DarkOliveGreen/>
+                <text x=30 y=70 font-family="Arial" data-id="greeting">"Hello World"</text>
+                for r in radii {
+                    <circle r={r} cx={r * 2.} stroke=Paint::None/>
+                }
+                if show_border {
+                    <rect width=Percentage::from(100) height=Percentage::from(100) fill=Paint::None/>
+                } else {
+                    <title>"no border"</title>
+                }
+                {legend.clone()}
+            </svg>
+        };
+
+        let expected = Svg::new()
+            .width(500.)
+            .height(500.)
+            .add_child(Rect::new(200., 400.).x(20.).fill(Color::DarkOliveGreen))
+            .add_child(
+                Text::new()
+                    .x(30.)
+                    .y(70.)
+                    .font_family("Arial")
+                    .data("id", "greeting")
+                    .add_child("Hello World".to_string()),
+            )
+            .add_children(radii.map(|r| Circle::new(r).cx(r * 2.).stroke(Paint::None)))
+            .add_child(Rect::new(Percentage::from(100.), Percentage::from(100.)).fill(Paint::None))
+            .add_child(legend);
+        assert_eq!(svg.to_string(), expected.to_string());
+    }
 }
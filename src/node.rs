@@ -0,0 +1,170 @@
+use crate::Shape;
+use crate::serialize::SerializeOptions;
+use crate::types::{AttributeError, Attributes, Color};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// A node that is not one of the generated elements, such as a component from another crate.
+///
+/// Nodes are stored in [`Shape::Custom`] through [`Shape::custom`]. Implementing a category
+/// trait such as `ShapeElement` along with `From<Node> for Shape` lets a node be passed to the
+/// matching `add_child_*` methods
+pub trait SvgNode: CloneSvgNode + Debug + Send + Sync {
+    fn tag_name(&self) -> &str;
+
+    fn attributes(&self) -> Attributes;
+
+    fn child_nodes(&self) -> &[Shape] {
+        &[]
+    }
+
+    fn write_svg(&self, svg: &mut String, options: &SerializeOptions) {
+        write_element(
+            svg,
+            self.tag_name(),
+            &self.attributes(),
+            self.child_nodes(),
+            options,
+        );
+    }
+
+    /// Replaces every color of the node and its descendants, see
+    /// [`Shape::map_colors`](crate::Shape::map_colors). Nodes are left untouched by default
+    fn map_colors(&mut self, _f: &mut dyn FnMut(&Color) -> Color) {}
+}
+
+/// Lets `Box<dyn SvgNode>` be cloned, implemented for every `SvgNode + Clone`
+pub trait CloneSvgNode {
+    fn clone_box(&self) -> Box<dyn SvgNode>;
+}
+
+impl<T: SvgNode + Clone + 'static> CloneSvgNode for T {
+    fn clone_box(&self) -> Box<dyn SvgNode> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn SvgNode> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// A custom node as plain data, which is what custom nodes are deserialized into
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenericNode {
+    tag_name: String,
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    attributes: Attributes,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    children: Vec<Shape>,
+}
+
+impl GenericNode {
+    pub fn new<N: Into<String>>(tag_name: N) -> Self {
+        Self {
+            tag_name: tag_name.into(),
+            attributes: Attributes::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// A snapshot of any node's tag name, attributes and children
+    pub fn from_node(node: &dyn SvgNode) -> Self {
+        Self {
+            tag_name: node.tag_name().to_string(),
+            attributes: node.attributes(),
+            children: node.child_nodes().to_vec(),
+        }
+    }
+
+    /// # Panics
+    /// If `name` is not a valid attribute name
+    pub fn attr<N: Into<String>, V: ToString>(self, name: N, value: V) -> Self {
+        self.try_attr(name, value)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_attr<N: Into<String>, V: ToString>(
+        mut self,
+        name: N,
+        value: V,
+    ) -> Result<Self, AttributeError> {
+        self.attributes
+            .try_insert(name.into(), value.to_string(), &[])?;
+        Ok(self)
+    }
+
+    pub fn add_child<T: Into<Shape>>(mut self, child: T) -> Self {
+        self.children.push(child.into());
+        self
+    }
+}
+
+impl SvgNode for GenericNode {
+    fn tag_name(&self) -> &str {
+        &self.tag_name
+    }
+
+    fn attributes(&self) -> Attributes {
+        self.attributes.clone()
+    }
+
+    fn child_nodes(&self) -> &[Shape] {
+        &self.children
+    }
+
+    fn map_colors(&mut self, f: &mut dyn FnMut(&Color) -> Color) {
+        self.attributes.map_colors(f);
+        for child in self.children.iter_mut() {
+            child.map_colors(f);
+        }
+    }
+}
+
+impl From<GenericNode> for Shape {
+    fn from(node: GenericNode) -> Self {
+        Shape::custom(node)
+    }
+}
+
+/// Writes `<tag_name attributes>children</tag_name>`, self-closing when there are no children
+pub fn write_element(
+    svg: &mut String,
+    tag_name: &str,
+    attributes: &Attributes,
+    children: &[Shape],
+    options: &SerializeOptions,
+) {
+    svg.push_str(&format!("<{}{}", tag_name, attributes));
+    if children.is_empty() {
+        svg.push_str("/>");
+        return;
+    }
+
+    svg.push('>');
+    for child in children {
+        child.write_svg(svg, options);
+    }
+    svg.push_str(&format!("</{}>", tag_name));
+}
+
+/// Serde support for [`Shape::Custom`], which goes through [`GenericNode`]
+pub(crate) mod custom_node {
+    use super::{GenericNode, SvgNode};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[allow(clippy::borrowed_box)]
+    pub fn serialize<S: Serializer>(
+        node: &Box<dyn SvgNode>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        GenericNode::from_node(node.as_ref()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Box<dyn SvgNode>, D::Error> {
+        Ok(Box::new(GenericNode::deserialize(deserializer)?))
+    }
+}
@@ -0,0 +1,56 @@
+//! Ready-made markers for the `marker-start`, `marker-mid` and `marker-end` attributes.
+//!
+//! Each marker is sized relative to the stroke width of the shape it is drawn on, so
+//! an arrowhead stays proportional to the line it terminates. Add the returned marker
+//! to the document and reference it with [`MarkerReference::url`](crate::types::MarkerReference::url).
+
+use crate::elements::{Circle, Marker, Path, Rect};
+use crate::types::{Color, MarkerUnits, Orient, PathData};
+
+fn marker(id: &str, width: f64, height: f64) -> Marker {
+    Marker::new()
+        .id(id)
+        .view_box((0., 0., 10., 10.))
+        .marker_units(MarkerUnits::StrokeWidth)
+        .marker_width(width)
+        .marker_height(height)
+}
+
+/// A filled triangular arrowhead pointing along the path direction, reversed at the start.
+pub fn arrowhead(id: &str, color: Color) -> Marker {
+    marker(id, 6., 6.)
+        .ref_x(10.)
+        .ref_y(5.)
+        .orient(Orient::AutoStartReverse)
+        .add_child(Path::new(PathData::new().M(0., 0.).L(10., 5.).L(0., 10.).z()).fill(color))
+}
+
+/// A chevron arrowhead drawn with a stroke instead of a fill.
+pub fn open_arrowhead(id: &str, color: Color) -> Marker {
+    marker(id, 6., 6.)
+        .ref_x(9.)
+        .ref_y(5.)
+        .orient(Orient::AutoStartReverse)
+        .add_child(
+            Path::new(PathData::new().M(1., 1.).L(9., 5.).L(1., 9.))
+                .fill(Color::Transparent)
+                .stroke(color)
+                .stroke_width(1.5),
+        )
+}
+
+/// A filled circle centered on the vertex.
+pub fn dot(id: &str, color: Color) -> Marker {
+    marker(id, 4., 4.)
+        .ref_x(5.)
+        .ref_y(5.)
+        .add_child(Circle::new(5.).cx(5.).cy(5.).fill(color))
+}
+
+/// A filled square centered on the vertex.
+pub fn square(id: &str, color: Color) -> Marker {
+    marker(id, 4., 4.)
+        .ref_x(5.)
+        .ref_y(5.)
+        .add_child(Rect::new(10., 10.).fill(color))
+}
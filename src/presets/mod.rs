@@ -0,0 +1,2 @@
+pub mod markers;
+pub mod patterns;
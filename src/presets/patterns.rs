@@ -0,0 +1,55 @@
+//! Ready-made tiling patterns for `fill` and `stroke`, mostly useful for monochrome output.
+//!
+//! Every pattern tiles in user space so the density stays the same regardless of the size
+//! of the shape being filled. Add the returned pattern to the document and reference it
+//! with [`Paint::url`](crate::types::Paint::url).
+
+use crate::elements::{Circle, Path, Pattern, Rect};
+use crate::types::{Color, PathData, Units};
+
+fn tile(id: &str, width: f64, height: f64) -> Pattern {
+    Pattern::new(width, height)
+        .id(id)
+        .pattern_units(Units::UserSpaceOnUse)
+}
+
+/// Parallel lines `spacing` apart, rotated by `angle` degrees.
+pub fn hatching(id: &str, color: Color, spacing: f64, stroke_width: f64, angle: f64) -> Pattern {
+    // lines run through the middle of the tile, as the tile clips strokes on its edges
+    let center = spacing / 2.;
+    tile(id, spacing, spacing)
+        .pattern_transform(format!("rotate({})", angle))
+        .add_child(
+            Path::new(PathData::new().M(center, 0.).V(spacing))
+                .stroke(color)
+                .stroke_width(stroke_width),
+        )
+}
+
+/// Two sets of perpendicular lines `spacing` apart, rotated by `angle` degrees.
+pub fn cross_hatching(
+    id: &str,
+    color: Color,
+    spacing: f64,
+    stroke_width: f64,
+    angle: f64,
+) -> Pattern {
+    hatching(id, color.clone(), spacing, stroke_width, angle).add_child(
+        Path::new(PathData::new().M(0., spacing / 2.).H(spacing))
+            .stroke(color)
+            .stroke_width(stroke_width),
+    )
+}
+
+/// A grid of dots of the given `radius`, `spacing` apart.
+pub fn dots(id: &str, color: Color, spacing: f64, radius: f64) -> Pattern {
+    let center = spacing / 2.;
+    tile(id, spacing, spacing).add_child(Circle::new(radius).cx(center).cy(center).fill(color))
+}
+
+/// Alternating squares of `size`, with the gaps left transparent.
+pub fn checkerboard(id: &str, color: Color, size: f64) -> Pattern {
+    tile(id, size * 2., size * 2.)
+        .add_child(Rect::new(size, size).fill(color.clone()))
+        .add_child(Rect::new(size, size).x(size).y(size).fill(color))
+}
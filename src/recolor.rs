@@ -0,0 +1,187 @@
+//! Color passes over finished documents, such as dark mode variants or color vision deficiency
+//! simulations.
+//!
+//! [`Shape::map_colors`](crate::Shape::map_colors) replaces every color in `fill`, `stroke`,
+//! other color attributes, `style` declarations and `<style>` stylesheets with the result of a
+//! function, like the transforms in this module, as in
+//! `document.map_colors(&mut recolor::grayscale)`.
+//!
+//! Colors without an sRGB value, such as `currentColor` and `var(...)`, are left as they are.
+//! Transformed colors keep their format where it can express the result, see
+//! [`ColorFormat::of`].
+
+use crate::types::color_spaces::{from_linear, gamut_map, oklab_to_srgb, srgb_to_oklab, to_linear};
+use crate::types::{Color, ColorFormat};
+
+/// Attributes and CSS properties whose value is a color, or a paint with a fallback color
+pub(crate) const COLOR_PROPERTIES: [&str; 6] = [
+    "fill",
+    "stroke",
+    "stop-color",
+    "flood-color",
+    "lighting-color",
+    "color",
+];
+
+/// Maps the sRGB channels of a color, leaving fully transparent colors and those without an sRGB
+/// value untouched
+fn map_srgb(color: &Color, f: impl FnOnce([f64; 3]) -> [f64; 3]) -> Color {
+    match color.to_srgba() {
+        Some([r, g, b, a]) if a > 0. => {
+            let [r, g, b] = f([r, g, b]);
+            Color::from_srgba([r, g, b, a], ColorFormat::of(color))
+        }
+        _ => color.clone(),
+    }
+}
+
+/// Applies a matrix to the linear sRGB channels
+fn map_linear(color: &Color, matrix: &[[f64; 3]; 3]) -> Color {
+    map_srgb(color, |srgb| {
+        let [r, g, b] = srgb.map(to_linear);
+        matrix
+            .map(|[x, y, z]| x * r + y * g + z * b)
+            .map(from_linear)
+    })
+}
+
+/// Inverts the Oklab lightness, so light backgrounds turn dark and dark text light while hues
+/// and saturation stay recognizable
+pub fn dark_mode(color: &Color) -> Color {
+    map_srgb(color, |srgb| {
+        let [l, a, b] = srgb_to_oklab(srgb);
+        gamut_map(oklab_to_srgb([1. - l, a, b]))
+    })
+}
+
+/// A gray of the same relative luminance
+pub fn grayscale(color: &Color) -> Color {
+    map_srgb(color, |srgb| {
+        let [r, g, b] = srgb.map(to_linear);
+        [from_linear(0.2126 * r + 0.7152 * g + 0.0722 * b); 3]
+    })
+}
+
+/// How the color appears without red cones, per Machado et al. (2009)
+pub fn protanopia(color: &Color) -> Color {
+    map_linear(
+        color,
+        &[
+            [0.152286, 1.052583, -0.204868],
+            [0.114503, 0.786281, 0.099216],
+            [-0.003882, -0.048116, 1.051998],
+        ],
+    )
+}
+
+/// How the color appears without green cones, per Machado et al. (2009)
+pub fn deuteranopia(color: &Color) -> Color {
+    map_linear(
+        color,
+        &[
+            [0.367322, 0.860646, -0.227968],
+            [0.280085, 0.672501, 0.047413],
+            [-0.011820, 0.042940, 0.968881],
+        ],
+    )
+}
+
+/// How the color appears without blue cones, per Machado et al. (2009)
+pub fn tritanopia(color: &Color) -> Color {
+    map_linear(
+        color,
+        &[
+            [1.255528, -0.076749, -0.178779],
+            [-0.078411, 0.930809, 0.147602],
+            [0.004733, 0.691367, 0.303900],
+        ],
+    )
+}
+
+/// Replaces the colors of a `fill`, `stroke` or other color attribute value, which may also be a
+/// `url(...)` paint with a fallback color. Returns whether the value held a color
+pub(crate) fn map_value_colors(value: &mut String, f: &mut dyn FnMut(&Color) -> Color) -> bool {
+    let (prefix, color) = if value.trim_start().starts_with("url(") {
+        match value.rfind(')') {
+            Some(end) => value.split_at(end + 1),
+            None => return false,
+        }
+    } else {
+        ("", value.as_str())
+    };
+    let (color, important) = match color.trim().strip_suffix("!important") {
+        Some(color) => (color, " !important"),
+        None => (color.trim(), ""),
+    };
+    let Ok(parsed) = color.trim().parse::<Color>() else {
+        return false;
+    };
+    let separator = if prefix.is_empty() { "" } else { " " };
+    *value = format!("{}{}{}{}", prefix, separator, f(&parsed), important);
+    true
+}
+
+/// Replaces the colors of the color properties among CSS declarations, such as a `style`
+/// attribute, or within the `selector { ... }` rules and `@media` blocks of a stylesheet, leaving
+/// everything else as written. Custom properties such as `--primary` are mapped when their value
+/// is a color
+pub fn map_css_colors(css: &mut String, f: &mut dyn FnMut(&Color) -> Color) {
+    let bytes = css.as_bytes();
+    let mut mapped = String::with_capacity(css.len());
+    let mut start = 0;
+    let mut quote = None;
+    let mut parentheses = 0usize;
+    let mut index = 0;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        match quote {
+            Some(_) if byte == b'\\' => index += 1,
+            Some(open) if byte == open => quote = None,
+            Some(_) => {}
+            None => match byte {
+                b'"' | b'\'' => quote = Some(byte),
+                b'(' => parentheses += 1,
+                b')' => parentheses = parentheses.saturating_sub(1),
+                b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                    index = css[index + 2..]
+                        .find("*/")
+                        .map_or(bytes.len(), |end| index + end + 4);
+                    continue;
+                }
+                b'{' | b'}' | b';' if parentheses == 0 => {
+                    let segment = &css[start..index];
+                    if byte == b'{' {
+                        // A selector or at-rule prelude, such as `:root` or `@media (...)`
+                        mapped.push_str(segment);
+                    } else {
+                        mapped.push_str(&map_declaration(segment, f));
+                    }
+                    mapped.push(byte as char);
+                    start = index + 1;
+                }
+                _ => {}
+            },
+        }
+        index += 1;
+    }
+    mapped.push_str(&map_declaration(&css[start..], f));
+    *css = mapped;
+}
+
+/// Maps the value of one `property: value` declaration when the property takes a color
+fn map_declaration(declaration: &str, f: &mut dyn FnMut(&Color) -> Color) -> String {
+    let Some((property, value)) = declaration.split_once(':') else {
+        return declaration.to_string();
+    };
+    let name = property.trim().to_ascii_lowercase();
+    if !name.starts_with("--") && !COLOR_PROPERTIES.contains(&name.as_str()) {
+        return declaration.to_string();
+    }
+    let mut mapped = value.trim().to_string();
+    if !map_value_colors(&mut mapped, f) {
+        return declaration.to_string();
+    }
+    let leading = &value[..value.len() - value.trim_start().len()];
+    let trailing = &value[value.trim_end().len()..];
+    format!("{}:{}{}{}", property, leading, mapped, trailing)
+}
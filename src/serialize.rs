@@ -0,0 +1,47 @@
+use crate::theme::Theme;
+
+/// Options controlling how elements are written as SVG markup
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SerializeOptions {
+    omit_defaults: bool,
+    srgb_fallback: bool,
+    theme: Option<Theme>,
+}
+
+impl SerializeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Skip attributes whose value equals the spec default, producing smaller output. Inherited
+    /// properties such as `fill` are always written, as they override the value of the parent
+    pub fn omit_defaults(mut self, omit_defaults: bool) -> Self {
+        self.omit_defaults = omit_defaults;
+        self
+    }
+
+    pub fn omits_defaults(&self) -> bool {
+        self.omit_defaults
+    }
+
+    /// Write Lab, LCh, Oklab, Oklch, HWB and Display P3 colors as gamut mapped `rgb()`, for
+    /// renderers that only support sRGB
+    pub fn srgb_fallback(mut self, srgb_fallback: bool) -> Self {
+        self.srgb_fallback = srgb_fallback;
+        self
+    }
+
+    pub fn has_srgb_fallback(&self) -> bool {
+        self.srgb_fallback
+    }
+
+    /// Write tokens as the colors and fonts `theme` gives them instead of as `var(--name)`
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    pub fn get_theme(&self) -> Option<&Theme> {
+        self.theme.as_ref()
+    }
+}
@@ -0,0 +1,142 @@
+//! Themes for documents authored with design tokens such as [`Token::Primary`].
+//!
+//! Tokens are written as CSS custom properties like `var(--primary)`. A [`Theme`] either
+//! resolves them while serializing, through [`SerializeOptions::theme`], or declares the
+//! custom properties in a `<style>` element from [`Theme::style_element`], which can switch to
+//! dark colors with `prefers-color-scheme`.
+
+use crate::elements::Style;
+use crate::serialize::SerializeOptions;
+use crate::types::{Color, FontFamily, Token};
+
+/// Colors and fonts for tokens, with optional colors for dark mode
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Theme {
+    colors: Vec<(Token, Color)>,
+    dark_colors: Vec<(Token, Color)>,
+    fonts: Vec<(Token, FontFamily)>,
+}
+
+impl Theme {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn color<C: Into<Color>>(mut self, token: Token, color: C) -> Self {
+        insert(&mut self.colors, token, color.into());
+        self
+    }
+
+    /// The color of `token` when the viewer prefers a dark color scheme
+    pub fn dark_color<C: Into<Color>>(mut self, token: Token, color: C) -> Self {
+        insert(&mut self.dark_colors, token, color.into());
+        self
+    }
+
+    /// A `font-family` value, such as `Inter, sans-serif`, or another font token
+    pub fn font<F: Into<FontFamily>>(mut self, token: Token, family: F) -> Self {
+        insert(&mut self.fonts, token, family.into());
+        self
+    }
+
+    pub fn get_color(&self, token: &Token) -> Option<&Color> {
+        get(&self.colors, token)
+    }
+
+    /// The color of `token`, following tokens whose color is another token. `None` when the
+    /// chain ends in a token without a color or loops back on itself
+    pub fn resolve_color(&self, token: &Token) -> Option<&Color> {
+        let mut visited = vec![token];
+        let mut color = self.get_color(token)?;
+        while let Color::Token(next) = color {
+            if visited.contains(&next) {
+                return None;
+            }
+            visited.push(next);
+            color = self.get_color(next)?;
+        }
+        Some(color)
+    }
+
+    pub fn get_dark_color(&self, token: &Token) -> Option<&Color> {
+        get(&self.dark_colors, token)
+    }
+
+    pub fn get_font(&self, token: &Token) -> Option<&FontFamily> {
+        get(&self.fonts, token)
+    }
+
+    /// The font families of `token`, following tokens whose font is another token. `None` when
+    /// the chain ends in a token without a font or loops back on itself
+    pub fn resolve_font(&self, token: &Token) -> Option<&str> {
+        let mut visited = vec![token];
+        let mut font = self.get_font(token)?;
+        loop {
+            match font {
+                FontFamily::Names(names) => return Some(names),
+                FontFamily::Token(next) => {
+                    if visited.contains(&next) {
+                        return None;
+                    }
+                    visited.push(next);
+                    font = self.get_font(next)?;
+                }
+            }
+        }
+    }
+
+    /// The theme with its dark colors in place of the light ones, for resolving the dark variant
+    /// of a document
+    pub fn to_dark(&self) -> Theme {
+        let mut dark = self.clone();
+        for (token, color) in &self.dark_colors {
+            insert(&mut dark.colors, token.clone(), color.clone());
+        }
+        dark
+    }
+
+    /// CSS declaring every token as a custom property on `:root`, followed by a
+    /// `prefers-color-scheme: dark` media query when there are dark colors
+    pub fn to_css(&self, options: &SerializeOptions) -> String {
+        let declarations = |colors: &[(Token, Color)], fonts: &[(Token, FontFamily)]| {
+            let colors = colors
+                .iter()
+                .map(|(token, color)| (token, color.to_svg_string(options)));
+            let fonts = fonts.iter().map(|(token, font)| (token, font.to_string()));
+            colors
+                .chain(fonts)
+                .map(|(token, value)| format!(" --{}: {};", token.name(), value))
+                .collect::<String>()
+        };
+
+        let mut css = format!(":root {{{} }}", declarations(&self.colors, &self.fonts));
+        if !self.dark_colors.is_empty() {
+            css.push_str(&format!(
+                " @media (prefers-color-scheme: dark) {{ :root {{{} }} }}",
+                declarations(&self.dark_colors, &[])
+            ));
+        }
+        css
+    }
+
+    /// A `<style>` element with [`Theme::to_css`], to add to the root `svg` when the tokens are
+    /// left as custom properties. A `]]>` in a theme value is split across two CDATA sections
+    pub fn style_element(&self, options: &SerializeOptions) -> Style {
+        let css = self.to_css(options).replace("]]>", "]]]]><![CDATA[>");
+        Style::new().add_child(format!("<![CDATA[{}]]>", css))
+    }
+}
+
+fn insert<T>(entries: &mut Vec<(Token, T)>, token: Token, value: T) {
+    match entries.iter_mut().find(|(existing, _)| *existing == token) {
+        Some((_, existing)) => *existing = value,
+        None => entries.push((token, value)),
+    }
+}
+
+fn get<'a, T>(entries: &'a [(Token, T)], token: &Token) -> Option<&'a T> {
+    entries
+        .iter()
+        .find(|(existing, _)| existing == token)
+        .map(|(_, value)| value)
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// ARIA roles relevant to SVG content, including the Graphics ARIA module roles
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AriaRole {
+    GraphicsDocument,
+    GraphicsObject,
+    GraphicsSymbol,
+    Img,
+    Figure,
+    Group,
+    List,
+    ListItem,
+    Link,
+    Button,
+    Heading,
+    Table,
+    Row,
+    Cell,
+    Region,
+    Note,
+    Presentation,
+    None,
+    Custom(String),
+}
+
+impl Display for AriaRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match &self {
+            AriaRole::GraphicsDocument => "graphics-document",
+            AriaRole::GraphicsObject => "graphics-object",
+            AriaRole::GraphicsSymbol => "graphics-symbol",
+            AriaRole::Img => "img",
+            AriaRole::Figure => "figure",
+            AriaRole::Group => "group",
+            AriaRole::List => "list",
+            AriaRole::ListItem => "listitem",
+            AriaRole::Link => "link",
+            AriaRole::Button => "button",
+            AriaRole::Heading => "heading",
+            AriaRole::Table => "table",
+            AriaRole::Row => "row",
+            AriaRole::Cell => "cell",
+            AriaRole::Region => "region",
+            AriaRole::Note => "note",
+            AriaRole::Presentation => "presentation",
+            AriaRole::None => "none",
+            AriaRole::Custom(str) => str,
+        };
+        write!(f, "{}", str)
+    }
+}
@@ -0,0 +1,138 @@
+use crate::recolor::{COLOR_PROPERTIES, map_css_colors, map_value_colors};
+use crate::types::Color;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Untyped attributes set with `attr` and `data`, written in insertion order after the typed ones
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Attributes {
+    entries: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeError {
+    /// The name is not a valid XML attribute name, or a `data-*` key contains uppercase letters
+    InvalidName(String),
+    /// The name belongs to a typed attribute of the element, which should be set through its builder
+    Collision(String),
+}
+
+impl Display for AttributeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttributeError::InvalidName(name) => {
+                write!(f, "`{}` is not a valid attribute name", name)
+            }
+            AttributeError::Collision(name) => write!(
+                f,
+                "`{}` is a typed attribute of this element, use its builder method instead",
+                name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AttributeError {}
+
+impl Attributes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(entry_name, _)| entry_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Sets `name`, replacing an earlier value in place, unless the name is invalid or one of `typed`
+    pub fn try_insert(
+        &mut self,
+        name: String,
+        value: String,
+        typed: &[&str],
+    ) -> Result<(), AttributeError> {
+        if !is_valid_name(&name) {
+            return Err(AttributeError::InvalidName(name));
+        }
+        if typed.contains(&name.as_str()) {
+            return Err(AttributeError::Collision(name));
+        }
+
+        match self
+            .entries
+            .iter_mut()
+            .find(|(entry_name, _)| *entry_name == name)
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.entries.push((name, value)),
+        }
+        Ok(())
+    }
+
+    /// Replaces the colors of color attributes such as `fill` and `stop-color`, and of `style`
+    pub fn map_colors(&mut self, f: &mut dyn FnMut(&Color) -> Color) {
+        for (name, value) in self.entries.iter_mut() {
+            if name == "style" {
+                map_css_colors(value, f);
+            } else if COLOR_PROPERTIES.contains(&name.as_str()) {
+                map_value_colors(value, f);
+            }
+        }
+    }
+
+    /// The full attribute name for a `data-*` key
+    pub fn data_name(key: &str) -> Result<String, AttributeError> {
+        let name = format!("data-{}", key);
+        if key.is_empty() || key.chars().any(|c| c.is_ascii_uppercase()) {
+            return Err(AttributeError::InvalidName(name));
+        }
+        Ok(name)
+    }
+}
+
+impl Display for Attributes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, value) in &self.entries {
+            write!(f, " {}=\"{}\"", name, escape_attribute(value))?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether `name` is a valid XML attribute name
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    (first.is_alphabetic() || first == '_' || first == ':')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+}
+
+/// Escapes a value for a double-quoted attribute, as used for every attribute generated elements
+/// write
+pub fn escape_attribute(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,5 +1,12 @@
+use crate::serialize::SerializeOptions;
+use crate::types::Token;
+use crate::types::color_spaces::{
+    display_p3_to_srgb, from_polar, gamut_map, hwb_to_srgb, lab_to_srgb, oklab_to_srgb,
+};
+use crate::types::named_colors::NAMED_COLORS;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Color {
@@ -164,8 +171,31 @@ pub enum Color {
 
     Hsla(u16, u8, u8, f32),
 
+    /// CIE Lab: lightness from 0 to 100, the `a` and `b` axes and alpha
+    Lab(f32, f32, f32, f32),
+
+    /// CIE LCh: lightness from 0 to 100, chroma, hue in degrees and alpha
+    Lch(f32, f32, f32, f32),
+
+    /// Oklab: lightness from 0 to 1, the `a` and `b` axes and alpha
+    Oklab(f32, f32, f32, f32),
+
+    /// Oklch: lightness from 0 to 1, chroma, hue in degrees and alpha
+    Oklch(f32, f32, f32, f32),
+
+    /// Hue in degrees, whiteness and blackness from 0 to 100 and alpha
+    Hwb(f32, f32, f32, f32),
+
+    /// `color(display-p3 r g b)` with channels from 0 to 1, and alpha
+    DisplayP3(f32, f32, f32, f32),
+
+    /// `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`
     Hex(String),
 
+    /// Written as `var(--name)`, or as the color a [`Theme`](crate::theme::Theme) gives it
+    Token(Token),
+
+    /// Written as is, for values no other variant covers such as `var(--primary)`
     Custom(String),
 }
 
@@ -328,9 +358,402 @@ impl Display for Color {
             Color::Rgba(r, g, b, a) => format!("rgba({}, {}, {}, {})", r, g, b, a),
             Color::Hsl(h, s, l) => format!("hsl({}, {}%, {}%)", h, s, l),
             Color::Hsla(h, s, l, a) => format!("hsla({}, {}%, {}%, {})", h, s, l, a),
+            Color::Lab(l, a, b, alpha) => format!("lab({} {} {}{})", l, a, b, alpha_suffix(*alpha)),
+            Color::Lch(l, c, h, alpha) => format!("lch({} {} {}{})", l, c, h, alpha_suffix(*alpha)),
+            Color::Oklab(l, a, b, alpha) => {
+                format!("oklab({} {} {}{})", l, a, b, alpha_suffix(*alpha))
+            }
+            Color::Oklch(l, c, h, alpha) => {
+                format!("oklch({} {} {}{})", l, c, h, alpha_suffix(*alpha))
+            }
+            Color::Hwb(h, w, b, alpha) => {
+                format!("hwb({} {}% {}%{})", h, w, b, alpha_suffix(*alpha))
+            }
+            Color::DisplayP3(r, g, b, alpha) => format!(
+                "color(display-p3 {} {} {}{})",
+                r,
+                g,
+                b,
+                alpha_suffix(*alpha)
+            ),
             Color::Hex(hex) => hex.to_string(),
+            Color::Token(token) => token.to_string(),
             Color::Custom(s) => s.to_string(),
         };
         write!(f, "{}", str)
     }
 }
+
+/// ` / alpha` for the space separated color functions, or nothing when opaque
+fn alpha_suffix(alpha: f32) -> String {
+    if alpha >= 1. {
+        String::new()
+    } else {
+        format!(" / {}", alpha)
+    }
+}
+
+impl Color {
+    /// Replaces the color with the result of `f`, like `map_colors` on elements
+    pub fn map_colors(&mut self, f: &mut dyn FnMut(&Color) -> Color) {
+        *self = f(self);
+    }
+
+    /// The attribute value, which is the gamut mapped `rgb()` of colors outside of CSS Color 3
+    /// when [`SerializeOptions::srgb_fallback`] is set, and the theme's color for a token when
+    /// [`SerializeOptions::theme`] defines it
+    pub fn to_svg_string(&self, options: &SerializeOptions) -> String {
+        if let Color::Token(token) = self {
+            return match options
+                .get_theme()
+                .and_then(|theme| theme.resolve_color(token))
+            {
+                Some(color) => color.to_svg_string(options),
+                None => token.to_string(),
+            };
+        }
+        let is_modern = matches!(
+            self,
+            Color::Lab(..)
+                | Color::Lch(..)
+                | Color::Oklab(..)
+                | Color::Oklch(..)
+                | Color::Hwb(..)
+                | Color::DisplayP3(..)
+        );
+        match self.to_rgba() {
+            Some(fallback) if is_modern && options.has_srgb_fallback() => fallback.to_string(),
+            _ => self.to_string(),
+        }
+    }
+
+    /// sRGB channels and alpha in `0.0..=1.0`, or `None` when the color depends on context.
+    ///
+    /// Colors outside of sRGB, which the CSS Color 4 spaces can express, are gamut mapped
+    pub fn to_srgba(&self) -> Option<[f64; 4]> {
+        let mapped = |srgb: [f64; 3], alpha: &f32| {
+            let [r, g, b] = gamut_map(srgb);
+            [r, g, b, f64::from(*alpha)]
+        };
+        let f64s = |x: &f32, y: &f32, z: &f32| [f64::from(*x), f64::from(*y), f64::from(*z)];
+        let rgb = |r: u8, g: u8, b: u8, a: f64| {
+            [
+                f64::from(r) / 255.,
+                f64::from(g) / 255.,
+                f64::from(b) / 255.,
+                a,
+            ]
+        };
+        match self {
+            Color::Transparent => Some([0., 0., 0., 0.]),
+            Color::CurrentColor | Color::Token(_) | Color::Custom(_) => None,
+            Color::Rgb(r, g, b) => Some(rgb(*r, *g, *b, 1.)),
+            Color::Rgba(r, g, b, a) => Some(rgb(*r, *g, *b, f64::from(*a))),
+            Color::Hsl(h, s, l) => Some(hsl_to_srgba(*h, *s, *l, 1.)),
+            Color::Hsla(h, s, l, a) => Some(hsl_to_srgba(*h, *s, *l, f64::from(*a))),
+            Color::Lab(l, a, b, alpha) => Some(mapped(lab_to_srgb(f64s(l, a, b)), alpha)),
+            Color::Lch(l, c, h, alpha) => {
+                Some(mapped(lab_to_srgb(from_polar(f64s(l, c, h))), alpha))
+            }
+            Color::Oklab(l, a, b, alpha) => Some(mapped(oklab_to_srgb(f64s(l, a, b)), alpha)),
+            Color::Oklch(l, c, h, alpha) => {
+                Some(mapped(oklab_to_srgb(from_polar(f64s(l, c, h))), alpha))
+            }
+            Color::Hwb(h, w, b, alpha) => {
+                let [h, w, b] = f64s(h, w, b);
+                Some(mapped(hwb_to_srgb([h, w / 100., b / 100.]), alpha))
+            }
+            Color::DisplayP3(r, g, b, alpha) => {
+                Some(mapped(display_p3_to_srgb(f64s(r, g, b)), alpha))
+            }
+            Color::Hex(hex) => parse_hex(hex).map(|[r, g, b, a]| rgb(r, g, b, f64::from(a) / 255.)),
+            named => NAMED_COLORS
+                .iter()
+                .find(|(color, _, _)| color == named)
+                .map(|(_, _, [r, g, b])| rgb(*r, *g, *b, 1.)),
+        }
+    }
+
+    /// WCAG 2 relative luminance, ignoring alpha
+    pub fn relative_luminance(&self) -> Option<f64> {
+        let linear = |channel: f64| {
+            if channel <= 0.04045 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let [r, g, b, _] = self.to_srgba()?;
+        Some(0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b))
+    }
+
+    /// WCAG 2 contrast ratio between two colors, from 1 to 21
+    pub fn contrast_ratio(&self, other: &Color) -> Option<f64> {
+        let a = self.relative_luminance()?;
+        let b = other.relative_luminance()?;
+        Some((a.max(b) + 0.05) / (a.min(b) + 0.05))
+    }
+}
+
+fn hsl_to_srgba(h: u16, s: u8, l: u8, a: f64) -> [f64; 4] {
+    let [r, g, b] = hsl_to_srgb(
+        f64::from(h),
+        f64::from(s.min(100)) / 100.,
+        f64::from(l.min(100)) / 100.,
+    );
+    [r, g, b, a]
+}
+
+/// Hue in degrees, saturation and lightness in `0.0..=1.0`
+pub(crate) fn hsl_to_srgb(h: f64, s: f64, l: f64) -> [f64; 3] {
+    let h = h.rem_euclid(360.) / 360.;
+    let channel = |n: f64| {
+        let k = (n + h * 12.) % 12.;
+        let a = s * l.min(1. - l);
+        l - a * (k - 3.).min(9. - k).clamp(-1., 1.)
+    };
+    [channel(0.), channel(8.), channel(4.)]
+}
+
+/// Parses `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa` into rgba bytes
+fn parse_hex(hex: &str) -> Option<[u8; 4]> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let byte = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+    let nibble = |i: usize| u8::from_str_radix(&digits[i..=i], 16).ok().map(|n| n * 17);
+    match digits.len() {
+        3 => Some([nibble(0)?, nibble(1)?, nibble(2)?, 255]),
+        4 => Some([nibble(0)?, nibble(1)?, nibble(2)?, nibble(3)?]),
+        6 => Some([byte(0)?, byte(2)?, byte(4)?, 255]),
+        8 => Some([byte(0)?, byte(2)?, byte(4)?, byte(6)?]),
+        _ => None,
+    }
+}
+
+/// Returned when parsing a [`Color`] from a string that is not a CSS color
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseColorError {
+    /// Not a named color, hex color or color function
+    Unknown(String),
+    /// A `#` color that is not 3, 4, 6 or 8 hex digits
+    InvalidHex(String),
+    /// A color function with the wrong number or kind of arguments
+    InvalidFunction(String),
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseColorError::Unknown(color) => write!(f, "`{}` is not a color", color),
+            ParseColorError::InvalidHex(color) => {
+                write!(f, "`{}` is not a valid hex color", color)
+            }
+            ParseColorError::InvalidFunction(color) => {
+                write!(f, "`{}` has invalid color function arguments", color)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+/// Parses named colors case-insensitively, `transparent`, `currentColor`, hex colors,
+/// `rgb()`, `rgba()`, `hsl()` and `hsla()` in both comma and space separated syntax, and
+/// `lab()`, `lch()`, `oklab()`, `oklch()`, `hwb()` and `color(display-p3 ...)`
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let color = s.trim();
+        if color.starts_with('#') {
+            return match parse_hex(color) {
+                Some(_) => Ok(Color::Hex(color.to_string())),
+                None => Err(ParseColorError::InvalidHex(s.to_string())),
+            };
+        }
+        if color.eq_ignore_ascii_case("transparent") {
+            return Ok(Color::Transparent);
+        }
+        if color.eq_ignore_ascii_case("currentColor") {
+            return Ok(Color::CurrentColor);
+        }
+        if let Some((named, _, _)) = NAMED_COLORS
+            .iter()
+            .find(|(_, name, _)| color.eq_ignore_ascii_case(name))
+        {
+            return Ok(named.clone());
+        }
+
+        let Some((function, arguments)) = color
+            .strip_suffix(')')
+            .and_then(|color| color.split_once('('))
+        else {
+            return Err(ParseColorError::Unknown(s.to_string()));
+        };
+        let function = function.trim().to_ascii_lowercase();
+        let invalid = || ParseColorError::InvalidFunction(s.to_string());
+        if function == "color" {
+            let arguments = arguments.trim_start();
+            let Some(arguments) = arguments
+                .get(.."display-p3".len())
+                .filter(|space| space.eq_ignore_ascii_case("display-p3"))
+                .map(|space| &arguments[space.len()..])
+            else {
+                return Err(ParseColorError::Unknown(s.to_string()));
+            };
+            let ([r, g, b], alpha) = split_modern_arguments(arguments).ok_or_else(invalid)?;
+            let channel = |value| parse_component(value, 1.).ok_or_else(invalid);
+            return Ok(Color::DisplayP3(
+                channel(r)?,
+                channel(g)?,
+                channel(b)?,
+                alpha,
+            ));
+        }
+        if ["lab", "lch", "oklab", "oklch", "hwb"].contains(&function.as_str()) {
+            let ([x, y, z], alpha) = split_modern_arguments(arguments).ok_or_else(invalid)?;
+            let component = |value, percent| parse_component(value, percent).ok_or_else(invalid);
+            let hue = |value| parse_angle(value).map(|hue| hue as f32).ok_or_else(invalid);
+            return Ok(match function.as_str() {
+                "lab" => Color::Lab(
+                    component(x, 100.)?,
+                    component(y, 125.)?,
+                    component(z, 125.)?,
+                    alpha,
+                ),
+                "lch" => Color::Lch(component(x, 100.)?, component(y, 150.)?, hue(z)?, alpha),
+                "oklab" => Color::Oklab(
+                    component(x, 1.)?,
+                    component(y, 0.4)?,
+                    component(z, 0.4)?,
+                    alpha,
+                ),
+                "oklch" => Color::Oklch(component(x, 1.)?, component(y, 0.4)?, hue(z)?, alpha),
+                _ => Color::Hwb(hue(x)?, component(y, 100.)?, component(z, 100.)?, alpha),
+            });
+        }
+        if !["rgb", "rgba", "hsl", "hsla"].contains(&function.as_str()) {
+            return Err(ParseColorError::Unknown(s.to_string()));
+        }
+        let (channels, alpha) = split_arguments(arguments).ok_or_else(invalid)?;
+        let alpha = match alpha {
+            Some(alpha) => Some(parse_alpha(alpha).ok_or_else(invalid)?),
+            None => None,
+        };
+        if function.starts_with("rgb") {
+            let [r, g, b] = channels;
+            let r = parse_rgb_channel(r).ok_or_else(invalid)?;
+            let g = parse_rgb_channel(g).ok_or_else(invalid)?;
+            let b = parse_rgb_channel(b).ok_or_else(invalid)?;
+            return Ok(match alpha {
+                Some(a) => Color::Rgba(r, g, b, a),
+                None => Color::Rgb(r, g, b),
+            });
+        }
+
+        let [h, s, l] = channels;
+        let h = parse_hue(h).ok_or_else(invalid)?;
+        let s = parse_percentage(s).ok_or_else(invalid)?;
+        let l = parse_percentage(l).ok_or_else(invalid)?;
+        Ok(match alpha {
+            Some(a) => Color::Hsla(h, s, l, a),
+            None => Color::Hsl(h, s, l),
+        })
+    }
+}
+
+/// Splits `a, b, c[, alpha]` or `a b c[ / alpha]` into three channels and the alpha
+fn split_arguments(arguments: &str) -> Option<([&str; 3], Option<&str>)> {
+    let (channels, alpha) = if arguments.contains(',') {
+        let mut parts = arguments.split(',').map(str::trim).collect::<Vec<_>>();
+        let alpha = if parts.len() == 4 { parts.pop() } else { None };
+        (parts, alpha)
+    } else {
+        let (channels, alpha) = match arguments.split_once('/') {
+            Some((channels, alpha)) => (channels, Some(alpha.trim())),
+            None => (arguments, None),
+        };
+        (channels.split_whitespace().collect(), alpha)
+    };
+    Some((channels.try_into().ok()?, alpha))
+}
+
+/// The space separated syntax of the CSS Color 4 functions, with alpha defaulting to 1
+fn split_modern_arguments(arguments: &str) -> Option<([&str; 3], f32)> {
+    if arguments.contains(',') {
+        return None;
+    }
+    let (channels, alpha) = split_arguments(arguments)?;
+    let alpha = match alpha {
+        Some(alpha) => parse_alpha(alpha)?,
+        None => 1.,
+    };
+    Some((channels, alpha))
+}
+
+/// A number, or a percentage of `percent`, the value `100%` stands for. `none` is zero
+fn parse_component(value: &str, percent: f64) -> Option<f32> {
+    if value.eq_ignore_ascii_case("none") {
+        return Some(0.);
+    }
+    let component = match value.strip_suffix('%') {
+        Some(percentage) => parse_number(percentage)? * percent / 100.,
+        None => parse_number(value)?,
+    };
+    Some(component as f32)
+}
+
+fn parse_number(value: &str) -> Option<f64> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+/// A number from 0 to 255 or a percentage, rounded and clamped
+fn parse_rgb_channel(value: &str) -> Option<u8> {
+    let channel = match value.strip_suffix('%') {
+        Some(percentage) => parse_number(percentage)? * 255. / 100.,
+        None => parse_number(value)?,
+    };
+    Some(channel.round().clamp(0., 255.) as u8)
+}
+
+/// Degrees, optionally with a `deg`, `grad`, `rad` or `turn` unit, wrapped into `0..360`
+fn parse_hue(value: &str) -> Option<u16> {
+    Some(parse_angle(value)?.round().rem_euclid(360.) as u16)
+}
+
+/// Degrees, optionally with a `deg`, `grad`, `rad` or `turn` unit. `none` is zero
+fn parse_angle(value: &str) -> Option<f64> {
+    if value.eq_ignore_ascii_case("none") {
+        return Some(0.);
+    }
+    let units = [
+        ("deg", 1.),
+        ("grad", 0.9),
+        ("rad", 180. / std::f64::consts::PI),
+        ("turn", 360.),
+    ];
+    units
+        .iter()
+        .find_map(|(unit, scale)| Some(parse_number(value.strip_suffix(unit)?)? * scale))
+        .or_else(|| parse_number(value))
+}
+
+/// A saturation or lightness from 0 to 100, with or without `%`
+fn parse_percentage(value: &str) -> Option<u8> {
+    let percentage = parse_number(value.strip_suffix('%').unwrap_or(value))?;
+    Some(percentage.round().clamp(0., 100.) as u8)
+}
+
+/// A number from 0 to 1 or a percentage, clamped
+fn parse_alpha(value: &str) -> Option<f32> {
+    let alpha = match value.strip_suffix('%') {
+        Some(percentage) => parse_number(percentage)? / 100.,
+        None => parse_number(value)?,
+    };
+    Some(alpha.clamp(0., 1.) as f32)
+}
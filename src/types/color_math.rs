@@ -0,0 +1,222 @@
+use crate::types::Color;
+use crate::types::color::hsl_to_srgb;
+use crate::types::color_spaces::{
+    srgb_to_display_p3, srgb_to_hwb, srgb_to_lab, srgb_to_oklab, to_polar,
+};
+
+/// How the result of a color conversion or operation is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorFormat {
+    /// `#rrggbb`, or `#rrggbbaa` when translucent
+    Hex,
+    /// `rgb()`, or `rgba()` when translucent
+    Rgb,
+    /// `hsl()`, or `hsla()` when translucent
+    Hsl,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+    Hwb,
+    /// `color(display-p3 r g b)`
+    DisplayP3,
+}
+
+impl ColorFormat {
+    /// Hex, HSL and the CSS Color 4 colors keep their format, everything else becomes `rgb()`
+    pub fn of(color: &Color) -> Self {
+        match color {
+            Color::Hex(_) => ColorFormat::Hex,
+            Color::Hsl(..) | Color::Hsla(..) => ColorFormat::Hsl,
+            Color::Lab(..) => ColorFormat::Lab,
+            Color::Lch(..) => ColorFormat::Lch,
+            Color::Oklab(..) => ColorFormat::Oklab,
+            Color::Oklch(..) => ColorFormat::Oklch,
+            Color::Hwb(..) => ColorFormat::Hwb,
+            Color::DisplayP3(..) => ColorFormat::DisplayP3,
+            _ => ColorFormat::Rgb,
+        }
+    }
+}
+
+/// Conversions and operations work on the sRGB value of a color, so they return `None` for
+/// `currentColor` and custom colors, except that the HSL operations change the components of
+/// HSL colors directly. Results keep the format of `self`, see [`ColorFormat::of`]
+impl Color {
+    /// A color from sRGB channels and alpha in `0.0..=1.0`, which are clamped
+    pub fn from_srgba([r, g, b, a]: [f64; 4], format: ColorFormat) -> Color {
+        let byte = |channel: f64| (channel.clamp(0., 1.) * 255.).round() as u8;
+        let a = a.clamp(0., 1.);
+        let opaque = a >= 1.;
+        let srgb = [r, g, b].map(|channel| channel.clamp(0., 1.));
+        match format {
+            ColorFormat::Hex if opaque => {
+                Color::Hex(format!("#{:02x}{:02x}{:02x}", byte(r), byte(g), byte(b)))
+            }
+            ColorFormat::Hex => Color::Hex(format!(
+                "#{:02x}{:02x}{:02x}{:02x}",
+                byte(r),
+                byte(g),
+                byte(b),
+                byte(a)
+            )),
+            ColorFormat::Rgb if opaque => Color::Rgb(byte(r), byte(g), byte(b)),
+            ColorFormat::Rgb => Color::Rgba(byte(r), byte(g), byte(b), a as f32),
+            ColorFormat::Hsl => {
+                let [h, s, l] = srgb_to_hsl(srgb);
+                let h = h.round() as u16 % 360;
+                let percent = |value: f64| (value * 100.).round() as u8;
+                if opaque {
+                    Color::Hsl(h, percent(s), percent(l))
+                } else {
+                    Color::Hsla(h, percent(s), percent(l), a as f32)
+                }
+            }
+            ColorFormat::Lab => {
+                let [l, a_, b_] = srgb_to_lab(srgb).map(|value| round(value, 2));
+                Color::Lab(l, a_, b_, a as f32)
+            }
+            ColorFormat::Lch => {
+                let [l, c, h] = polar(to_polar(srgb_to_lab(srgb)), 2);
+                Color::Lch(l, c, h, a as f32)
+            }
+            ColorFormat::Oklab => {
+                let [l, a_, b_] = srgb_to_oklab(srgb).map(|value| round(value, 4));
+                Color::Oklab(l, a_, b_, a as f32)
+            }
+            ColorFormat::Oklch => {
+                let [l, c, h] = polar(to_polar(srgb_to_oklab(srgb)), 4);
+                Color::Oklch(l, c, h, a as f32)
+            }
+            ColorFormat::Hwb => {
+                let [h, w, b_] = srgb_to_hwb(srgb);
+                Color::Hwb(
+                    round(h, 2),
+                    round(w * 100., 2),
+                    round(b_ * 100., 2),
+                    a as f32,
+                )
+            }
+            ColorFormat::DisplayP3 => {
+                let [r, g, b] = srgb_to_display_p3(srgb).map(|value| round(value, 4));
+                Color::DisplayP3(r, g, b, a as f32)
+            }
+        }
+    }
+
+    pub fn to_format(&self, format: ColorFormat) -> Option<Color> {
+        Some(Color::from_srgba(self.to_srgba()?, format))
+    }
+
+    /// `rgb()` or `rgba()`
+    pub fn to_rgba(&self) -> Option<Color> {
+        self.to_format(ColorFormat::Rgb)
+    }
+
+    /// `hsl()` or `hsla()`
+    pub fn to_hsla(&self) -> Option<Color> {
+        self.to_format(ColorFormat::Hsl)
+    }
+
+    /// Adds `amount` to the HSL lightness, which goes from 0 to 1
+    pub fn lighten(&self, amount: f64) -> Option<Color> {
+        self.map_hsl(|[h, s, l]| [h, s, l + amount])
+    }
+
+    /// Subtracts `amount` from the HSL lightness, which goes from 0 to 1
+    pub fn darken(&self, amount: f64) -> Option<Color> {
+        self.lighten(-amount)
+    }
+
+    /// Adds `amount` to the HSL saturation, which goes from 0 to 1
+    pub fn saturate(&self, amount: f64) -> Option<Color> {
+        self.map_hsl(|[h, s, l]| [h, s + amount, l])
+    }
+
+    /// Subtracts `amount` from the HSL saturation, which goes from 0 to 1
+    pub fn desaturate(&self, amount: f64) -> Option<Color> {
+        self.saturate(-amount)
+    }
+
+    /// Rotates the hue by `degrees`
+    pub fn rotate_hue(&self, degrees: f64) -> Option<Color> {
+        self.map_hsl(|[h, s, l]| [h + degrees, s, l])
+    }
+
+    pub fn with_alpha(&self, alpha: f64) -> Option<Color> {
+        let [r, g, b, _] = self.to_srgba()?;
+        Some(Color::from_srgba([r, g, b, alpha], ColorFormat::of(self)))
+    }
+
+    /// Inverts the sRGB channels, keeping alpha
+    pub fn invert(&self) -> Option<Color> {
+        let [r, g, b, a] = self.to_srgba()?;
+        Some(Color::from_srgba(
+            [1. - r, 1. - g, 1. - b, a],
+            ColorFormat::of(self),
+        ))
+    }
+
+    /// Interpolates the sRGB channels and alpha, `weight` 0 gives `self` and 1 gives `other`
+    pub fn mix(&self, other: &Color, weight: f64) -> Option<Color> {
+        let from = self.to_srgba()?;
+        let to = other.to_srgba()?;
+        let weight = weight.clamp(0., 1.);
+        let mixed = [0, 1, 2, 3].map(|i| from[i] + (to[i] - from[i]) * weight);
+        Some(Color::from_srgba(mixed, ColorFormat::of(self)))
+    }
+
+    fn map_hsl(&self, f: impl FnOnce([f64; 3]) -> [f64; 3]) -> Option<Color> {
+        // HSL colors are changed in place, so grays keep their hue and nothing is lost to sRGB
+        let (h, s, l, alpha) = match *self {
+            Color::Hsl(h, s, l) => (h, s, l, None),
+            Color::Hsla(h, s, l, a) => (h, s, l, Some(a)),
+            _ => {
+                let [r, g, b, a] = self.to_srgba()?;
+                let [h, s, l] = f(srgb_to_hsl([r, g, b]));
+                let [r, g, b] = hsl_to_srgb(h, s.clamp(0., 1.), l.clamp(0., 1.));
+                return Some(Color::from_srgba([r, g, b, a], ColorFormat::of(self)));
+            }
+        };
+        let [h, s, l] = f([f64::from(h), f64::from(s) / 100., f64::from(l) / 100.]);
+        let h = h.rem_euclid(360.).round() as u16 % 360;
+        let percent = |value: f64| (value.clamp(0., 1.) * 100.).round() as u8;
+        Some(match alpha {
+            Some(a) => Color::Hsla(h, percent(s), percent(l), a),
+            None => Color::Hsl(h, percent(s), percent(l)),
+        })
+    }
+}
+
+/// Rounds to `decimals` places, without producing `-0`
+fn round(value: f64, decimals: i32) -> f32 {
+    let scale = 10f64.powi(decimals);
+    ((value * scale).round() / scale + 0.) as f32
+}
+
+/// Rounds lightness, chroma and hue, dropping the hue of achromatic colors
+fn polar([l, c, h]: [f64; 3], decimals: i32) -> [f32; 3] {
+    let c = round(c, decimals);
+    let h = if c == 0. { 0. } else { round(h, 2) % 360. };
+    [round(l, decimals), c, h]
+}
+
+/// Hue in degrees, saturation and lightness in `0.0..=1.0`
+pub(crate) fn srgb_to_hsl([r, g, b]: [f64; 3]) -> [f64; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.;
+    let delta = max - min;
+    if delta == 0. {
+        return [0., 0., l];
+    }
+    let s = delta / (1. - (2. * l - 1.).abs());
+    let h = if max == r {
+        ((g - b) / delta).rem_euclid(6.)
+    } else if max == g {
+        (b - r) / delta + 2.
+    } else {
+        (r - g) / delta + 4.
+    };
+    [h * 60., s, l]
+}
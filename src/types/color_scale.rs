@@ -0,0 +1,371 @@
+use crate::types::color_spaces::{
+    from_polar, gamut_map, lab_to_srgb, oklab_to_srgb, srgb_to_lab, srgb_to_oklab, to_polar,
+};
+use crate::types::{Color, ColorFormat};
+
+/// The space the colors of a [`ColorScale`] are interpolated in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    Srgb,
+    Lab,
+    /// Lab with hue interpolated along the shorter arc
+    Lch,
+    #[default]
+    Oklab,
+    /// Oklab with hue interpolated along the shorter arc
+    Oklch,
+}
+
+impl ColorSpace {
+    fn convert_srgb(self, srgb: [f64; 3]) -> [f64; 3] {
+        match self {
+            ColorSpace::Srgb => srgb,
+            ColorSpace::Lab => srgb_to_lab(srgb),
+            ColorSpace::Lch => to_polar(srgb_to_lab(srgb)),
+            ColorSpace::Oklab => srgb_to_oklab(srgb),
+            ColorSpace::Oklch => to_polar(srgb_to_oklab(srgb)),
+        }
+    }
+
+    fn to_srgb(self, color: [f64; 3]) -> [f64; 3] {
+        match self {
+            ColorSpace::Srgb => color,
+            ColorSpace::Lab => lab_to_srgb(color),
+            ColorSpace::Lch => lab_to_srgb(from_polar(color)),
+            ColorSpace::Oklab => oklab_to_srgb(color),
+            ColorSpace::Oklch => oklab_to_srgb(from_polar(color)),
+        }
+    }
+
+    fn is_polar(self) -> bool {
+        matches!(self, ColorSpace::Lch | ColorSpace::Oklch)
+    }
+}
+
+/// Maps numbers to colors, either continuously by interpolating between colors or by
+/// picking one of a categorical palette
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorScale {
+    colors: Vec<[f64; 4]>,
+    categorical: bool,
+    space: ColorSpace,
+    format: ColorFormat,
+    domain: [f64; 2],
+    midpoint: Option<f64>,
+}
+
+impl ColorScale {
+    /// A continuous scale through `colors`, evenly spaced and interpolated in Oklab.
+    ///
+    /// Colors without an sRGB value, such as `currentColor`, are skipped. An empty scale samples
+    /// as transparent
+    pub fn new<C: Into<Color>>(colors: impl IntoIterator<Item = C>) -> Self {
+        ColorScale {
+            colors: colors
+                .into_iter()
+                .filter_map(|color| color.into().to_srgba())
+                .collect(),
+            categorical: false,
+            space: ColorSpace::default(),
+            format: ColorFormat::Hex,
+            domain: [0., 1.],
+            midpoint: None,
+        }
+    }
+
+    /// A palette of distinct colors, sampled without interpolation
+    pub fn categorical<C: Into<Color>>(colors: impl IntoIterator<Item = C>) -> Self {
+        ColorScale {
+            categorical: true,
+            ..ColorScale::new(colors)
+        }
+    }
+
+    /// A continuous scale from `low` through `mid` to `high`, see [`ColorScale::midpoint`]
+    pub fn diverging(low: impl Into<Color>, mid: impl Into<Color>, high: impl Into<Color>) -> Self {
+        ColorScale::new([low.into(), mid.into(), high.into()])
+    }
+
+    pub fn interpolation(mut self, space: ColorSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// The format of the sampled colors, hex by default
+    pub fn format(mut self, format: ColorFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// The values mapped to the start and end of the scale, `0` and `1` by default
+    pub fn domain(mut self, start: f64, end: f64) -> Self {
+        self.domain = [start, end];
+        self
+    }
+
+    /// The value mapped to the middle of the scale, so that a diverging scale stays centered
+    /// on it when the domain is not symmetric
+    pub fn midpoint(mut self, midpoint: f64) -> Self {
+        self.midpoint = Some(midpoint);
+        self
+    }
+
+    pub fn reversed(mut self) -> Self {
+        self.colors.reverse();
+        self
+    }
+
+    /// The color of `value`, which is clamped to the domain
+    pub fn sample(&self, value: f64) -> Color {
+        let [start, end] = self.domain;
+        let ratio = |numerator: f64, denominator: f64| {
+            if denominator == 0. {
+                0.
+            } else {
+                numerator / denominator
+            }
+        };
+        let t = match self.midpoint {
+            Some(mid) if (value - mid) * (end - mid) > 0. => {
+                0.5 + ratio(value - mid, end - mid) / 2.
+            }
+            Some(mid) => ratio(value - start, mid - start) / 2.,
+            None => ratio(value - start, end - start),
+        };
+        self.sample_normalized(t)
+    }
+
+    /// `n` colors, evenly spaced over a continuous scale or the first `n` of a palette, which
+    /// repeats when it has fewer
+    pub fn colors(&self, n: usize) -> Vec<Color> {
+        if self.categorical && !self.colors.is_empty() {
+            return (0..n)
+                .map(|i| Color::from_srgba(self.colors[i % self.colors.len()], self.format))
+                .collect();
+        }
+        (0..n)
+            .map(|i| match n {
+                1 => self.sample_normalized(0.5),
+                _ => self.sample_normalized(i as f64 / (n - 1) as f64),
+            })
+            .collect()
+    }
+
+    fn sample_normalized(&self, t: f64) -> Color {
+        let t = if t.is_nan() { 0. } else { t.clamp(0., 1.) };
+        let Some(last) = self.colors.len().checked_sub(1) else {
+            return Color::Transparent;
+        };
+        if self.categorical {
+            let index = ((t * self.colors.len() as f64) as usize).min(last);
+            return Color::from_srgba(self.colors[index], self.format);
+        }
+        if last == 0 {
+            return Color::from_srgba(self.colors[0], self.format);
+        }
+        let position = t * last as f64;
+        let index = (position.floor() as usize).min(last - 1);
+        let mixed = self.interpolate(
+            self.colors[index],
+            self.colors[index + 1],
+            position - index as f64,
+        );
+        Color::from_srgba(mixed, self.format)
+    }
+
+    fn interpolate(
+        &self,
+        [r0, g0, b0, a0]: [f64; 4],
+        [r1, g1, b1, a1]: [f64; 4],
+        weight: f64,
+    ) -> [f64; 4] {
+        let mut from = self.space.convert_srgb([r0, g0, b0]);
+        let mut to = self.space.convert_srgb([r1, g1, b1]);
+        if self.space.is_polar() {
+            // the hue of a gray is meaningless, so it takes that of the other color
+            const ACHROMATIC: f64 = 1e-4;
+            if from[1] < ACHROMATIC {
+                from[2] = to[2];
+            } else if to[1] < ACHROMATIC {
+                to[2] = from[2];
+            }
+            let difference = to[2] - from[2];
+            if difference > 180. {
+                from[2] += 360.;
+            } else if difference < -180. {
+                to[2] += 360.;
+            }
+        }
+        let mixed = [0, 1, 2].map(|i| from[i] + (to[i] - from[i]) * weight);
+        let [r, g, b] = gamut_map(self.space.to_srgb(mixed));
+        [r, g, b, a0 + (a1 - a0) * weight]
+    }
+
+    fn from_table(table: &[u32], categorical: bool) -> Self {
+        let colors = table.iter().map(|rgb| {
+            let [_, r, g, b] = rgb.to_be_bytes();
+            Color::Rgb(r, g, b)
+        });
+        let scale = if categorical {
+            ColorScale::categorical(colors)
+        } else {
+            ColorScale::new(colors)
+        };
+        // the tables sample maps defined in sRGB closely enough to interpolate linearly
+        scale.interpolation(ColorSpace::Srgb)
+    }
+
+    /// Perceptually uniform, from dark purple to yellow
+    pub fn viridis() -> Self {
+        ColorScale::from_table(&VIRIDIS, false)
+    }
+
+    /// Perceptually uniform, from black through purple and orange to pale yellow
+    pub fn magma() -> Self {
+        ColorScale::from_table(&MAGMA, false)
+    }
+
+    /// Perceptually uniform and readable with color vision deficiencies, from blue to yellow
+    pub fn cividis() -> Self {
+        ColorScale::from_table(&CIVIDIS, false)
+    }
+
+    /// A rainbow from dark blue to dark red
+    pub fn turbo() -> Self {
+        ColorScale::from_table(&TURBO, false)
+    }
+
+    pub fn tableau10() -> Self {
+        ColorScale::from_table(&TABLEAU10, true)
+    }
+
+    /// ColorBrewer Set1
+    pub fn set1() -> Self {
+        ColorScale::from_table(&SET1, true)
+    }
+
+    /// ColorBrewer Set2
+    pub fn set2() -> Self {
+        ColorScale::from_table(&SET2, true)
+    }
+
+    /// ColorBrewer Set3
+    pub fn set3() -> Self {
+        ColorScale::from_table(&SET3, true)
+    }
+
+    /// ColorBrewer Dark2
+    pub fn dark2() -> Self {
+        ColorScale::from_table(&DARK2, true)
+    }
+
+    /// ColorBrewer Paired
+    pub fn paired() -> Self {
+        ColorScale::from_table(&PAIRED, true)
+    }
+
+    /// ColorBrewer Pastel1
+    pub fn pastel1() -> Self {
+        ColorScale::from_table(&PASTEL1, true)
+    }
+
+    /// ColorBrewer Accent
+    pub fn accent() -> Self {
+        ColorScale::from_table(&ACCENT, true)
+    }
+
+    /// ColorBrewer RdBu, diverging from red to blue
+    pub fn red_blue() -> Self {
+        ColorScale::from_table(&RED_BLUE, false)
+    }
+
+    /// ColorBrewer RdYlBu, diverging from red through yellow to blue
+    pub fn red_yellow_blue() -> Self {
+        ColorScale::from_table(&RED_YELLOW_BLUE, false)
+    }
+
+    /// ColorBrewer PiYG, diverging from pink to green
+    pub fn pink_green() -> Self {
+        ColorScale::from_table(&PINK_GREEN, false)
+    }
+
+    /// ColorBrewer BrBG, diverging from brown to blue green
+    pub fn brown_blue_green() -> Self {
+        ColorScale::from_table(&BROWN_BLUE_GREEN, false)
+    }
+}
+
+const VIRIDIS: [u32; 11] = [
+    0x440154, 0x482475, 0x414487, 0x355f8d, 0x2a788e, 0x21918c, 0x22a884, 0x44bf70, 0x7ad151,
+    0xbddf26, 0xfde725,
+];
+
+const MAGMA: [u32; 11] = [
+    0x000004, 0x140e36, 0x3b0f70, 0x641a80, 0x8c2981, 0xb73779, 0xde4968, 0xf7705c, 0xfe9f6d,
+    0xfecf92, 0xfcfdbf,
+];
+
+const CIVIDIS: [u32; 11] = [
+    0x002051, 0x0a326a, 0x2b446e, 0x4d566d, 0x696970, 0x7f7c75, 0x948f78, 0xada476, 0xcaba6a,
+    0xead156, 0xfdea45,
+];
+
+const TURBO: [u32; 17] = [
+    0x23171b, 0x493eae, 0x4569ee, 0x3295f7, 0x26bce1, 0x29dcbc, 0x3ff393, 0x65fd6e, 0x95fb51,
+    0xc5ec3c, 0xecd12e, 0xffad24, 0xff821d, 0xf05616, 0xcb2f0d, 0xa31302, 0x900c00,
+];
+
+const TABLEAU10: [u32; 10] = [
+    0x4e79a7, 0xf28e2c, 0xe15759, 0x76b7b2, 0x59a14f, 0xedc949, 0xaf7aa1, 0xff9da7, 0x9c755f,
+    0xbab0ab,
+];
+
+const SET1: [u32; 9] = [
+    0xe41a1c, 0x377eb8, 0x4daf4a, 0x984ea3, 0xff7f00, 0xffff33, 0xa65628, 0xf781bf, 0x999999,
+];
+
+const SET2: [u32; 8] = [
+    0x66c2a5, 0xfc8d62, 0x8da0cb, 0xe78ac3, 0xa6d854, 0xffd92f, 0xe5c494, 0xb3b3b3,
+];
+
+const SET3: [u32; 12] = [
+    0x8dd3c7, 0xffffb3, 0xbebada, 0xfb8072, 0x80b1d3, 0xfdb462, 0xb3de69, 0xfccde5, 0xd9d9d9,
+    0xbc80bd, 0xccebc5, 0xffed6f,
+];
+
+const DARK2: [u32; 8] = [
+    0x1b9e77, 0xd95f02, 0x7570b3, 0xe7298a, 0x66a61e, 0xe6ab02, 0xa6761d, 0x666666,
+];
+
+const PAIRED: [u32; 12] = [
+    0xa6cee3, 0x1f78b4, 0xb2df8a, 0x33a02c, 0xfb9a99, 0xe31a1c, 0xfdbf6f, 0xff7f00, 0xcab2d6,
+    0x6a3d9a, 0xffff99, 0xb15928,
+];
+
+const PASTEL1: [u32; 9] = [
+    0xfbb4ae, 0xb3cde3, 0xccebc5, 0xdecbe4, 0xfed9a6, 0xffffcc, 0xe5d8bd, 0xfddaec, 0xf2f2f2,
+];
+
+const ACCENT: [u32; 8] = [
+    0x7fc97f, 0xbeaed4, 0xfdc086, 0xffff99, 0x386cb0, 0xf0027f, 0xbf5b17, 0x666666,
+];
+
+const RED_BLUE: [u32; 11] = [
+    0x67001f, 0xb2182b, 0xd6604d, 0xf4a582, 0xfddbc7, 0xf7f7f7, 0xd1e5f0, 0x92c5de, 0x4393c3,
+    0x2166ac, 0x053061,
+];
+
+const RED_YELLOW_BLUE: [u32; 11] = [
+    0xa50026, 0xd73027, 0xf46d43, 0xfdae61, 0xfee090, 0xffffbf, 0xe0f3f8, 0xabd9e9, 0x74add1,
+    0x4575b4, 0x313695,
+];
+
+const PINK_GREEN: [u32; 11] = [
+    0x8e0152, 0xc51b7d, 0xde77ae, 0xf1b6da, 0xfde0ef, 0xf7f7f7, 0xe6f5d0, 0xb8e186, 0x7fbc41,
+    0x4d9221, 0x276419,
+];
+
+const BROWN_BLUE_GREEN: [u32; 11] = [
+    0x543005, 0x8c510a, 0xbf812d, 0xdfc27d, 0xf6e8c3, 0xf5f5f5, 0xc7eae5, 0x80cdc1, 0x35978f,
+    0x01665e, 0x003c30,
+];
@@ -0,0 +1,271 @@
+//! Conversions between sRGB and the CSS Color 4 spaces, following the sample code of the spec.
+//!
+//! sRGB values here are gamma encoded and may fall outside `0.0..=1.0` until they are gamut
+//! mapped with [`gamut_map`].
+
+use crate::types::color::hsl_to_srgb;
+use crate::types::color_math::srgb_to_hsl;
+
+type Matrix = [[f64; 3]; 3];
+
+const LINEAR_SRGB_TO_XYZ: Matrix = [
+    [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+    [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+    [0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+];
+
+const XYZ_TO_LINEAR_SRGB: Matrix = [
+    [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+    [-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+    [
+        0.05563007969699366,
+        -0.20397695888897652,
+        1.0569715142428786,
+    ],
+];
+
+const LINEAR_P3_TO_XYZ: Matrix = [
+    [0.4865709486482162, 0.26566769316909306, 0.1982172852343625],
+    [0.2289745640697488, 0.6917385218365064, 0.079286914093745],
+    [0., 0.04511338185890264, 1.043944368900976],
+];
+
+const XYZ_TO_LINEAR_P3: Matrix = [
+    [2.493496911941425, -0.9313836179191239, -0.40271078445071684],
+    [
+        -0.8294889695615747,
+        1.7626640603183463,
+        0.023624685841943577,
+    ],
+    [
+        0.03584583024378447,
+        -0.07617238926804182,
+        0.9568845240076872,
+    ],
+];
+
+/// Bradford chromatic adaptation, as Lab is relative to D50 and sRGB to D65
+const D65_TO_D50: Matrix = [
+    [
+        1.0479297925449969,
+        0.022946870601609652,
+        -0.05019226628920524,
+    ],
+    [
+        0.02962780877005599,
+        0.9904344267538799,
+        -0.017073799063418826,
+    ],
+    [
+        -0.009243040646204504,
+        0.015055191490298152,
+        0.7518742814281371,
+    ],
+];
+
+const D50_TO_D65: Matrix = [
+    [0.955473421488075, -0.02309845494876471, 0.06325924320057072],
+    [
+        -0.0283697093338637,
+        1.0099953980813041,
+        0.021041441191917323,
+    ],
+    [
+        0.012314014864481998,
+        -0.020507649298898964,
+        1.330365926242124,
+    ],
+];
+
+const D50_WHITE: [f64; 3] = [0.3457 / 0.3585, 1., (1. - 0.3457 - 0.3585) / 0.3585];
+
+const LAB_EPSILON: f64 = 216. / 24389.;
+const LAB_KAPPA: f64 = 24389. / 27.;
+
+fn multiply(matrix: &Matrix, [x, y, z]: [f64; 3]) -> [f64; 3] {
+    matrix.map(|[a, b, c]| a * x + b * y + c * z)
+}
+
+/// The sRGB transfer function, extended to negative values
+pub(crate) fn to_linear(channel: f64) -> f64 {
+    let magnitude = channel.abs();
+    let linear = if magnitude <= 0.04045 {
+        magnitude / 12.92
+    } else {
+        ((magnitude + 0.055) / 1.055).powf(2.4)
+    };
+    linear.copysign(channel)
+}
+
+pub(crate) fn from_linear(channel: f64) -> f64 {
+    let magnitude = channel.abs();
+    let encoded = if magnitude <= 0.0031308 {
+        magnitude * 12.92
+    } else {
+        1.055 * magnitude.powf(1. / 2.4) - 0.055
+    };
+    encoded.copysign(channel)
+}
+
+fn srgb_to_xyz(srgb: [f64; 3]) -> [f64; 3] {
+    multiply(&LINEAR_SRGB_TO_XYZ, srgb.map(to_linear))
+}
+
+fn xyz_to_srgb(xyz: [f64; 3]) -> [f64; 3] {
+    multiply(&XYZ_TO_LINEAR_SRGB, xyz).map(from_linear)
+}
+
+pub(crate) fn lab_to_srgb([l, a, b]: [f64; 3]) -> [f64; 3] {
+    let fy = (l + 16.) / 116.;
+    let fx = a / 500. + fy;
+    let fz = fy - b / 200.;
+    let component = |f: f64| {
+        if f.powi(3) > LAB_EPSILON {
+            f.powi(3)
+        } else {
+            (116. * f - 16.) / LAB_KAPPA
+        }
+    };
+    let y = if l > LAB_KAPPA * LAB_EPSILON {
+        fy.powi(3)
+    } else {
+        l / LAB_KAPPA
+    };
+    let d50 = [component(fx), y, component(fz)];
+    let d50 = [0, 1, 2].map(|i| d50[i] * D50_WHITE[i]);
+    xyz_to_srgb(multiply(&D50_TO_D65, d50))
+}
+
+pub(crate) fn srgb_to_lab(srgb: [f64; 3]) -> [f64; 3] {
+    let d50 = multiply(&D65_TO_D50, srgb_to_xyz(srgb));
+    let [fx, fy, fz] = [0, 1, 2].map(|i| {
+        let value = d50[i] / D50_WHITE[i];
+        if value > LAB_EPSILON {
+            value.cbrt()
+        } else {
+            (LAB_KAPPA * value + 16.) / 116.
+        }
+    });
+    [116. * fy - 16., 500. * (fx - fy), 200. * (fy - fz)]
+}
+
+/// Lightness and the `a` and `b` axes to lightness, chroma and hue in degrees
+pub(crate) fn to_polar([l, a, b]: [f64; 3]) -> [f64; 3] {
+    [l, a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.)]
+}
+
+pub(crate) fn from_polar([l, c, h]: [f64; 3]) -> [f64; 3] {
+    let h = h.to_radians();
+    [l, c * h.cos(), c * h.sin()]
+}
+
+pub(crate) fn oklab_to_srgb([l, a, b]: [f64; 3]) -> [f64; 3] {
+    let l_ = l + 0.3963377774 * a + 0.2158037573 * b;
+    let m_ = l - 0.1055613458 * a - 0.0638541728 * b;
+    let s_ = l - 0.0894841775 * a - 1.2914855480 * b;
+    let [l, m, s] = [l_, m_, s_].map(|value| value.powi(3));
+    [
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+    ]
+    .map(from_linear)
+}
+
+pub(crate) fn srgb_to_oklab(srgb: [f64; 3]) -> [f64; 3] {
+    let [r, g, b] = srgb.map(to_linear);
+    let l = 0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b;
+    let m = 0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b;
+    let s = 0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b;
+    let [l_, m_, s_] = [l, m, s].map(f64::cbrt);
+    [
+        0.2104542553 * l_ + 0.7936177850 * m_ - 0.0040720468 * s_,
+        1.9779984951 * l_ - 2.4285922050 * m_ + 0.4505937099 * s_,
+        0.0259040371 * l_ + 0.7827717662 * m_ - 0.8086757660 * s_,
+    ]
+}
+
+pub(crate) fn display_p3_to_srgb(p3: [f64; 3]) -> [f64; 3] {
+    xyz_to_srgb(multiply(&LINEAR_P3_TO_XYZ, p3.map(to_linear)))
+}
+
+pub(crate) fn srgb_to_display_p3(srgb: [f64; 3]) -> [f64; 3] {
+    multiply(&XYZ_TO_LINEAR_P3, srgb_to_xyz(srgb)).map(from_linear)
+}
+
+/// Hue in degrees, whiteness and blackness in `0.0..=1.0`
+pub(crate) fn hwb_to_srgb([h, w, b]: [f64; 3]) -> [f64; 3] {
+    if w + b >= 1. {
+        let gray = w / (w + b);
+        return [gray; 3];
+    }
+    hsl_to_srgb(h, 1., 0.5).map(|channel| channel * (1. - w - b) + w)
+}
+
+pub(crate) fn srgb_to_hwb(srgb: [f64; 3]) -> [f64; 3] {
+    let [h, _, _] = srgb_to_hsl(srgb);
+    let [r, g, b] = srgb;
+    [h, r.min(g).min(b), 1. - r.max(g).max(b)]
+}
+
+fn in_gamut(srgb: [f64; 3]) -> bool {
+    srgb.iter()
+        .all(|channel| (-0.000001..=1.000001).contains(channel))
+}
+
+fn clip(srgb: [f64; 3]) -> [f64; 3] {
+    srgb.map(|channel| channel.clamp(0., 1.))
+}
+
+fn delta_e_ok(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let [l, a_, b_] = [0, 1, 2].map(|i| a[i] - b[i]);
+    (l * l + a_ * a_ + b_ * b_).sqrt()
+}
+
+/// Brings a color into sRGB by lowering its Oklch chroma until clipping it is imperceptible,
+/// the CSS Color 4 gamut mapping algorithm
+pub(crate) fn gamut_map(srgb: [f64; 3]) -> [f64; 3] {
+    const JUST_NOTICEABLE_DIFFERENCE: f64 = 0.02;
+    const EPSILON: f64 = 0.0001;
+
+    if in_gamut(srgb) {
+        return clip(srgb);
+    }
+    let origin = to_polar(srgb_to_oklab(srgb));
+    let [lightness, chroma, hue] = origin;
+    if lightness >= 1. {
+        return [1.; 3];
+    }
+    if lightness <= 0. {
+        return [0.; 3];
+    }
+
+    let to_srgb = |chroma: f64| oklab_to_srgb(from_polar([lightness, chroma, hue]));
+    let mut clipped = clip(srgb);
+    if delta_e_ok(srgb_to_oklab(clipped), from_polar(origin)) < JUST_NOTICEABLE_DIFFERENCE {
+        return clipped;
+    }
+
+    let (mut min, mut max) = (0., chroma);
+    let mut min_in_gamut = true;
+    while max - min > EPSILON {
+        let chroma = (min + max) / 2.;
+        let current = to_srgb(chroma);
+        if min_in_gamut && in_gamut(current) {
+            min = chroma;
+            continue;
+        }
+        clipped = clip(current);
+        let difference = delta_e_ok(srgb_to_oklab(clipped), from_polar([lightness, chroma, hue]));
+        if difference < JUST_NOTICEABLE_DIFFERENCE {
+            if JUST_NOTICEABLE_DIFFERENCE - difference < EPSILON {
+                return clipped;
+            }
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+    clipped
+}
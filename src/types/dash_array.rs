@@ -0,0 +1,53 @@
+use crate::types::List;
+use crate::types::unions::LengthOrPercentage;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// The value of `stroke-dasharray`: `none` for a solid stroke, or the lengths of alternating
+/// dashes and gaps
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum DashArray {
+    #[default]
+    None,
+    Dashes(List<LengthOrPercentage>),
+}
+
+impl Display for DashArray {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            // an empty list is a solid stroke as well, and `stroke-dasharray=""` is invalid
+            DashArray::Dashes(dashes) if !dashes.values().is_empty() => write!(f, "{}", dashes),
+            _ => write!(f, "none"),
+        }
+    }
+}
+
+impl From<List<LengthOrPercentage>> for DashArray {
+    fn from(dashes: List<LengthOrPercentage>) -> Self {
+        DashArray::Dashes(dashes)
+    }
+}
+
+impl<U: Into<LengthOrPercentage>> From<Vec<U>> for DashArray {
+    fn from(dashes: Vec<U>) -> Self {
+        DashArray::Dashes(dashes.into())
+    }
+}
+
+impl<U: Into<LengthOrPercentage>, const N: usize> From<[U; N]> for DashArray {
+    fn from(dashes: [U; N]) -> Self {
+        DashArray::Dashes(dashes.into())
+    }
+}
+
+impl From<f64> for DashArray {
+    fn from(dash: f64) -> Self {
+        DashArray::Dashes(dash.into())
+    }
+}
+
+impl From<i32> for DashArray {
+    fn from(dash: i32) -> Self {
+        DashArray::Dashes(dash.into())
+    }
+}
@@ -0,0 +1,53 @@
+use crate::serialize::SerializeOptions;
+use crate::types::Token;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// The value of `font-family`: a comma separated list of families, or a font token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FontFamily {
+    // tokens serialize as `var(--name)`, which no family name matches
+    Token(Token),
+    Names(String),
+}
+
+impl FontFamily {
+    /// The attribute value, with a token resolved when the theme of `options` defines it
+    pub fn to_svg_string(&self, options: &SerializeOptions) -> String {
+        match self {
+            FontFamily::Token(token) => options
+                .get_theme()
+                .and_then(|theme| theme.resolve_font(token))
+                .map_or_else(|| token.to_string(), str::to_string),
+            names => names.to_string(),
+        }
+    }
+}
+
+impl Display for FontFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FontFamily::Token(token) => write!(f, "{}", token),
+            FontFamily::Names(names) => write!(f, "{}", names),
+        }
+    }
+}
+
+impl From<&str> for FontFamily {
+    fn from(names: &str) -> Self {
+        FontFamily::Names(names.to_string())
+    }
+}
+
+impl From<String> for FontFamily {
+    fn from(names: String) -> Self {
+        FontFamily::Names(names)
+    }
+}
+
+impl From<Token> for FontFamily {
+    fn from(token: Token) -> Self {
+        FontFamily::Token(token)
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// A functional IRI reference to an element in the same document, written as `url(#id)`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FuncIri {
+    id: String,
+}
+
+impl FuncIri {
+    pub fn new<T: Into<String>>(id: T) -> Self {
+        Self { id: id.into() }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Display for FuncIri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "url(#{})", self.id)
+    }
+}
@@ -0,0 +1,38 @@
+use std::fmt::Display;
+
+/// Returned when parsing a keyword type from a string that is not one of its keywords
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseKeywordError {
+    keyword: String,
+    expected: &'static [&'static str],
+}
+
+impl ParseKeywordError {
+    pub fn new(keyword: &str, expected: &'static [&'static str]) -> Self {
+        Self {
+            keyword: keyword.to_string(),
+            expected,
+        }
+    }
+
+    pub fn keyword(&self) -> &str {
+        &self.keyword
+    }
+
+    pub fn expected(&self) -> &'static [&'static str] {
+        self.expected
+    }
+}
+
+impl Display for ParseKeywordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` is not one of `{}`",
+            self.keyword,
+            self.expected.join("`, `")
+        )
+    }
+}
+
+impl std::error::Error for ParseKeywordError {}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// A comma separated list of BCP 47 language tags, as used by `systemLanguage`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LanguageTags {
+    tags: Vec<String>,
+}
+
+impl LanguageTags {
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+}
+
+impl Display for LanguageTags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tags.join(", "))
+    }
+}
+
+impl From<&str> for LanguageTags {
+    fn from(tag: &str) -> Self {
+        Self {
+            tags: vec![tag.to_string()],
+        }
+    }
+}
+
+impl From<String> for LanguageTags {
+    fn from(tag: String) -> Self {
+        Self { tags: vec![tag] }
+    }
+}
+
+impl<T: Into<String>> From<Vec<T>> for LanguageTags {
+    fn from(tags: Vec<T>) -> Self {
+        Self {
+            tags: tags.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl<T: Into<String>, const N: usize> From<[T; N]> for LanguageTags {
+    fn from(tags: [T; N]) -> Self {
+        Self {
+            tags: tags.into_iter().map(Into::into).collect(),
+        }
+    }
+}
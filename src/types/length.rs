@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Length {
     length: f64,
 }
@@ -17,3 +17,9 @@ impl From<f64> for Length {
         Self { length }
     }
 }
+
+impl From<i32> for Length {
+    fn from(length: i32) -> Self {
+        f64::from(length).into()
+    }
+}
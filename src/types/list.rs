@@ -0,0 +1,105 @@
+use crate::types::unions::LengthOrPercentage;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// A whitespace separated list of values, e.g. `<list-of-length-percentages>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct List<T> {
+    values: Vec<T>,
+}
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        Self { values: Vec::new() }
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Display> Display for List<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let str = self
+            .values
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        write!(f, "{}", str)
+    }
+}
+
+impl<T, U: Into<T>> From<Vec<U>> for List<T> {
+    fn from(values: Vec<U>) -> Self {
+        Self {
+            values: values.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl<T, U: Into<T>, const N: usize> From<[U; N]> for List<T> {
+    fn from(values: [U; N]) -> Self {
+        Self {
+            values: values.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<f64> for List<f64> {
+    fn from(value: f64) -> Self {
+        Self {
+            values: vec![value],
+        }
+    }
+}
+
+impl From<i32> for List<f64> {
+    fn from(value: i32) -> Self {
+        f64::from(value).into()
+    }
+}
+
+impl From<f64> for List<LengthOrPercentage> {
+    fn from(value: f64) -> Self {
+        Self {
+            values: vec![value.into()],
+        }
+    }
+}
+
+impl From<i32> for List<LengthOrPercentage> {
+    fn from(value: i32) -> Self {
+        f64::from(value).into()
+    }
+}
+
+impl From<LengthOrPercentage> for List<LengthOrPercentage> {
+    fn from(value: LengthOrPercentage) -> Self {
+        Self {
+            values: vec![value],
+        }
+    }
+}
+
+impl From<&str> for List<String> {
+    fn from(value: &str) -> Self {
+        Self {
+            values: vec![value.to_string()],
+        }
+    }
+}
+
+impl From<String> for List<String> {
+    fn from(value: String) -> Self {
+        Self {
+            values: vec![value],
+        }
+    }
+}
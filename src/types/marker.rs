@@ -0,0 +1,59 @@
+use crate::types::FuncIri;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MarkerReference {
+    None,
+    Url(FuncIri),
+}
+
+impl MarkerReference {
+    pub fn url<T: Into<String>>(id: T) -> Self {
+        MarkerReference::Url(FuncIri::new(id))
+    }
+}
+
+impl Display for MarkerReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            MarkerReference::None => write!(f, "none"),
+            MarkerReference::Url(iri) => write!(f, "{}", iri),
+        }
+    }
+}
+
+impl From<FuncIri> for MarkerReference {
+    fn from(iri: FuncIri) -> Self {
+        MarkerReference::Url(iri)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Orient {
+    Auto,
+    AutoStartReverse,
+    Angle(f64),
+}
+
+impl Display for Orient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Orient::Auto => write!(f, "auto"),
+            Orient::AutoStartReverse => write!(f, "auto-start-reverse"),
+            Orient::Angle(angle) => write!(f, "{}", angle),
+        }
+    }
+}
+
+impl From<f64> for Orient {
+    fn from(angle: f64) -> Self {
+        Orient::Angle(angle)
+    }
+}
+
+impl From<i32> for Orient {
+    fn from(angle: i32) -> Self {
+        f64::from(angle).into()
+    }
+}
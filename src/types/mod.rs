@@ -2,6 +2,15 @@ pub mod unions;
 
 mod color;
 pub use color::*;
+mod color_math;
+pub use color_math::*;
+mod color_scale;
+pub(crate) mod color_spaces;
+pub use color_scale::*;
+mod token;
+pub use token::*;
+mod font_family;
+pub use font_family::*;
 mod path_data;
 pub use path_data::*;
 mod percentage;
@@ -18,3 +27,43 @@ pub use viewbox_size::*;
 
 mod preserve_aspect_ratio;
 pub use preserve_aspect_ratio::*;
+
+mod list;
+pub use list::*;
+
+mod iri;
+pub use iri::*;
+
+mod marker;
+pub use marker::*;
+
+mod dash_array;
+pub use dash_array::*;
+
+mod points;
+pub use points::*;
+
+mod paint;
+pub use paint::*;
+
+mod language_tags;
+pub use language_tags::*;
+
+mod xhtml;
+pub use xhtml::*;
+
+mod named_colors;
+
+mod aria;
+pub use aria::*;
+
+mod attributes;
+pub use attributes::*;
+
+mod keyword;
+pub use keyword::*;
+
+mod keywords {
+    include!(concat!(env!("OUT_DIR"), "/enums.rs"));
+}
+pub use keywords::*;
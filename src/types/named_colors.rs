@@ -0,0 +1,157 @@
+use crate::types::Color;
+
+/// sRGB channels of every CSS named color, in the order they are declared on [`Color`]
+pub(crate) static NAMED_COLORS: [(Color, &str, [u8; 3]); 148] = [
+    (Color::AliceBlue, "aliceblue", [240, 248, 255]),
+    (Color::AntiqueWhite, "antiquewhite", [250, 235, 215]),
+    (Color::Aqua, "aqua", [0, 255, 255]),
+    (Color::Aquamarine, "aquamarine", [127, 255, 212]),
+    (Color::Azure, "azure", [240, 255, 255]),
+    (Color::Beige, "beige", [245, 245, 220]),
+    (Color::Bisque, "bisque", [255, 228, 196]),
+    (Color::Black, "black", [0, 0, 0]),
+    (Color::BlanchedAlmond, "blanchedalmond", [255, 235, 205]),
+    (Color::Blue, "blue", [0, 0, 255]),
+    (Color::BlueViolet, "blueviolet", [138, 43, 226]),
+    (Color::Brown, "brown", [165, 42, 42]),
+    (Color::BurlyWood, "burlywood", [222, 184, 135]),
+    (Color::CadetBlue, "cadetblue", [95, 158, 160]),
+    (Color::Chartreuse, "chartreuse", [127, 255, 0]),
+    (Color::Chocolate, "chocolate", [210, 105, 30]),
+    (Color::Coral, "coral", [255, 127, 80]),
+    (Color::CornflowerBlue, "cornflowerblue", [100, 149, 237]),
+    (Color::Cornsilk, "cornsilk", [255, 248, 220]),
+    (Color::Crimson, "crimson", [220, 20, 60]),
+    (Color::Cyan, "cyan", [0, 255, 255]),
+    (Color::DarkBlue, "darkblue", [0, 0, 139]),
+    (Color::DarkCyan, "darkcyan", [0, 139, 139]),
+    (Color::DarkGoldenRod, "darkgoldenrod", [184, 134, 11]),
+    (Color::DarkGray, "darkgray", [169, 169, 169]),
+    (Color::DarkGrey, "darkgrey", [169, 169, 169]),
+    (Color::DarkGreen, "darkgreen", [0, 100, 0]),
+    (Color::DarkKhaki, "darkkhaki", [189, 183, 107]),
+    (Color::DarkMagenta, "darkmagenta", [139, 0, 139]),
+    (Color::DarkOliveGreen, "darkolivegreen", [85, 107, 47]),
+    (Color::DarkOrange, "darkorange", [255, 140, 0]),
+    (Color::DarkOrchid, "darkorchid", [153, 50, 204]),
+    (Color::DarkRed, "darkred", [139, 0, 0]),
+    (Color::DarkSalmon, "darksalmon", [233, 150, 122]),
+    (Color::DarkSeaGreen, "darkseagreen", [143, 188, 143]),
+    (Color::DarkSlateBlue, "darkslateblue", [72, 61, 139]),
+    (Color::DarkSlateGray, "darkslategray", [47, 79, 79]),
+    (Color::DarkSlateGrey, "darkslategrey", [47, 79, 79]),
+    (Color::DarkTurquoise, "darkturquoise", [0, 206, 209]),
+    (Color::DarkViolet, "darkviolet", [148, 0, 211]),
+    (Color::DeepPink, "deeppink", [255, 20, 147]),
+    (Color::DeepSkyBlue, "deepskyblue", [0, 191, 255]),
+    (Color::DimGray, "dimgray", [105, 105, 105]),
+    (Color::DimGrey, "dimgrey", [105, 105, 105]),
+    (Color::DodgerBlue, "dodgerblue", [30, 144, 255]),
+    (Color::FireBrick, "firebrick", [178, 34, 34]),
+    (Color::FloralWhite, "floralwhite", [255, 250, 240]),
+    (Color::ForestGreen, "forestgreen", [34, 139, 34]),
+    (Color::Fuchsia, "fuchsia", [255, 0, 255]),
+    (Color::Gainsboro, "gainsboro", [220, 220, 220]),
+    (Color::GhostWhite, "ghostwhite", [248, 248, 255]),
+    (Color::Gold, "gold", [255, 215, 0]),
+    (Color::GoldenRod, "goldenrod", [218, 165, 32]),
+    (Color::Gray, "gray", [128, 128, 128]),
+    (Color::Grey, "grey", [128, 128, 128]),
+    (Color::Green, "green", [0, 128, 0]),
+    (Color::GreenYellow, "greenyellow", [173, 255, 47]),
+    (Color::HoneyDew, "honeydew", [240, 255, 240]),
+    (Color::HotPink, "hotpink", [255, 105, 180]),
+    (Color::IndianRed, "indianred", [205, 92, 92]),
+    (Color::Indigo, "indigo", [75, 0, 130]),
+    (Color::Ivory, "ivory", [255, 255, 240]),
+    (Color::Khaki, "khaki", [240, 230, 140]),
+    (Color::Lavender, "lavender", [230, 230, 250]),
+    (Color::LavenderBlush, "lavenderblush", [255, 240, 245]),
+    (Color::LawnGreen, "lawngreen", [124, 252, 0]),
+    (Color::LemonChiffon, "lemonchiffon", [255, 250, 205]),
+    (Color::LightBlue, "lightblue", [173, 216, 230]),
+    (Color::LightCoral, "lightcoral", [240, 128, 128]),
+    (Color::LightCyan, "lightcyan", [224, 255, 255]),
+    (
+        Color::LightGoldenRodYellow,
+        "lightgoldenrodyellow",
+        [250, 250, 210],
+    ),
+    (Color::LightGray, "lightgray", [211, 211, 211]),
+    (Color::LightGrey, "lightgrey", [211, 211, 211]),
+    (Color::LightGreen, "lightgreen", [144, 238, 144]),
+    (Color::LightPink, "lightpink", [255, 182, 193]),
+    (Color::LightSalmon, "lightsalmon", [255, 160, 122]),
+    (Color::LightSeaGreen, "lightseagreen", [32, 178, 170]),
+    (Color::LightSkyBlue, "lightskyblue", [135, 206, 250]),
+    (Color::LightSlateGray, "lightslategray", [119, 136, 153]),
+    (Color::LightSlateGrey, "lightslategrey", [119, 136, 153]),
+    (Color::LightSteelBlue, "lightsteelblue", [176, 196, 222]),
+    (Color::LightYellow, "lightyellow", [255, 255, 224]),
+    (Color::Lime, "lime", [0, 255, 0]),
+    (Color::LimeGreen, "limegreen", [50, 205, 50]),
+    (Color::Linen, "linen", [250, 240, 230]),
+    (Color::Magenta, "magenta", [255, 0, 255]),
+    (Color::Maroon, "maroon", [128, 0, 0]),
+    (Color::MediumAquaMarine, "mediumaquamarine", [102, 205, 170]),
+    (Color::MediumBlue, "mediumblue", [0, 0, 205]),
+    (Color::MediumOrchid, "mediumorchid", [186, 85, 211]),
+    (Color::MediumPurple, "mediumpurple", [147, 112, 219]),
+    (Color::MediumSeaGreen, "mediumseagreen", [60, 179, 113]),
+    (Color::MediumSlateBlue, "mediumslateblue", [123, 104, 238]),
+    (Color::MediumSpringGreen, "mediumspringgreen", [0, 250, 154]),
+    (Color::MediumTurquoise, "mediumturquoise", [72, 209, 204]),
+    (Color::MediumVioletRed, "mediumvioletred", [199, 21, 133]),
+    (Color::MidnightBlue, "midnightblue", [25, 25, 112]),
+    (Color::MintCream, "mintcream", [245, 255, 250]),
+    (Color::MistyRose, "mistyrose", [255, 228, 225]),
+    (Color::Moccasin, "moccasin", [255, 228, 181]),
+    (Color::NavajoWhite, "navajowhite", [255, 222, 173]),
+    (Color::Navy, "navy", [0, 0, 128]),
+    (Color::OldLace, "oldlace", [253, 245, 230]),
+    (Color::Olive, "olive", [128, 128, 0]),
+    (Color::OliveDrab, "olivedrab", [107, 142, 35]),
+    (Color::Orange, "orange", [255, 165, 0]),
+    (Color::OrangeRed, "orangered", [255, 69, 0]),
+    (Color::Orchid, "orchid", [218, 112, 214]),
+    (Color::PaleGoldenRod, "palegoldenrod", [238, 232, 170]),
+    (Color::PaleGreen, "palegreen", [152, 251, 152]),
+    (Color::PaleTurquoise, "paleturquoise", [175, 238, 238]),
+    (Color::PaleVioletRed, "palevioletred", [219, 112, 147]),
+    (Color::PapayaWhip, "papayawhip", [255, 239, 213]),
+    (Color::PeachPuff, "peachpuff", [255, 218, 185]),
+    (Color::Peru, "peru", [205, 133, 63]),
+    (Color::Pink, "pink", [255, 192, 203]),
+    (Color::Plum, "plum", [221, 160, 221]),
+    (Color::PowderBlue, "powderblue", [176, 224, 230]),
+    (Color::Purple, "purple", [128, 0, 128]),
+    (Color::RebeccaPurple, "rebeccapurple", [102, 51, 153]),
+    (Color::Red, "red", [255, 0, 0]),
+    (Color::RosyBrown, "rosybrown", [188, 143, 143]),
+    (Color::RoyalBlue, "royalblue", [65, 105, 225]),
+    (Color::SaddleBrown, "saddlebrown", [139, 69, 19]),
+    (Color::Salmon, "salmon", [250, 128, 114]),
+    (Color::SandyBrown, "sandybrown", [244, 164, 96]),
+    (Color::SeaGreen, "seagreen", [46, 139, 87]),
+    (Color::SeaShell, "seashell", [255, 245, 238]),
+    (Color::Sienna, "sienna", [160, 82, 45]),
+    (Color::Silver, "silver", [192, 192, 192]),
+    (Color::SkyBlue, "skyblue", [135, 206, 235]),
+    (Color::SlateBlue, "slateblue", [106, 90, 205]),
+    (Color::SlateGray, "slategray", [112, 128, 144]),
+    (Color::SlateGrey, "slategrey", [112, 128, 144]),
+    (Color::Snow, "snow", [255, 250, 250]),
+    (Color::SpringGreen, "springgreen", [0, 255, 127]),
+    (Color::SteelBlue, "steelblue", [70, 130, 180]),
+    (Color::Tan, "tan", [210, 180, 140]),
+    (Color::Teal, "teal", [0, 128, 128]),
+    (Color::Thistle, "thistle", [216, 191, 216]),
+    (Color::Tomato, "tomato", [255, 99, 71]),
+    (Color::Turquoise, "turquoise", [64, 224, 208]),
+    (Color::Violet, "violet", [238, 130, 238]),
+    (Color::Wheat, "wheat", [245, 222, 179]),
+    (Color::White, "white", [255, 255, 255]),
+    (Color::WhiteSmoke, "whitesmoke", [245, 245, 245]),
+    (Color::Yellow, "yellow", [255, 255, 0]),
+    (Color::YellowGreen, "yellowgreen", [154, 205, 50]),
+];
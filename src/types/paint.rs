@@ -0,0 +1,65 @@
+use crate::serialize::SerializeOptions;
+use crate::types::{Color, FuncIri};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// The value of `fill` and `stroke`: a color, or a reference to a paint server such as a `pattern`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Paint {
+    None,
+    Color(Color),
+    /// A paint server reference with an optional fallback color used if the reference is invalid
+    Url(FuncIri, Option<Color>),
+    ContextFill,
+    ContextStroke,
+}
+
+impl Paint {
+    pub fn url<T: Into<String>>(id: T) -> Self {
+        Paint::Url(FuncIri::new(id), None)
+    }
+
+    /// Replaces the color, or the fallback color of a paint server reference, with the result of `f`
+    pub fn map_colors(&mut self, f: &mut dyn FnMut(&Color) -> Color) {
+        match self {
+            Paint::Color(color) | Paint::Url(_, Some(color)) => color.map_colors(f),
+            _ => {}
+        }
+    }
+
+    /// The attribute value, with colors written as [`Color::to_svg_string`] does
+    pub fn to_svg_string(&self, options: &SerializeOptions) -> String {
+        match self {
+            Paint::Color(color) => color.to_svg_string(options),
+            Paint::Url(iri, Some(fallback)) => {
+                format!("{} {}", iri, fallback.to_svg_string(options))
+            }
+            paint => paint.to_string(),
+        }
+    }
+}
+
+impl Display for Paint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Paint::None => write!(f, "none"),
+            Paint::Color(color) => write!(f, "{}", color),
+            Paint::Url(iri, None) => write!(f, "{}", iri),
+            Paint::Url(iri, Some(fallback)) => write!(f, "{} {}", iri, fallback),
+            Paint::ContextFill => write!(f, "context-fill"),
+            Paint::ContextStroke => write!(f, "context-stroke"),
+        }
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Paint::Color(color)
+    }
+}
+
+impl From<FuncIri> for Paint {
+    fn from(iri: FuncIri) -> Self {
+        Paint::Url(iri, None)
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::Formatter;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Percentage {
     percentage: f64,
 }
@@ -10,3 +10,15 @@ impl std::fmt::Display for Percentage {
         write!(f, "{}%", self.percentage)
     }
 }
+
+impl From<f64> for Percentage {
+    fn from(percentage: f64) -> Self {
+        Self { percentage }
+    }
+}
+
+impl From<i32> for Percentage {
+    fn from(percentage: i32) -> Self {
+        f64::from(percentage).into()
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Points {
+    points: Vec<(f64, f64)>,
+}
+
+impl Display for Points {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = self
+            .points
+            .iter()
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect::<Vec<_>>()
+            .join(" ");
+        write!(f, "{}", str)
+    }
+}
+
+impl From<Vec<(f64, f64)>> for Points {
+    fn from(points: Vec<(f64, f64)>) -> Self {
+        Self { points }
+    }
+}
+
+impl<const N: usize> From<[(f64, f64); N]> for Points {
+    fn from(points: [(f64, f64); N]) -> Self {
+        Self {
+            points: points.to_vec(),
+        }
+    }
+}
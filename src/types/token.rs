@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// A semantic design token, written as the CSS custom property `var(--name)` unless a
+/// [`Theme`](crate::theme::Theme) resolves it while serializing. Serde also uses the
+/// `var(--name)` form, so tokens don't collide with plain strings such as family names
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Token {
+    Primary,
+    Secondary,
+    Accent,
+    Background,
+    Surface,
+    Text,
+    MutedText,
+    Border,
+    Success,
+    Warning,
+    Danger,
+    BodyFont,
+    HeadingFont,
+    MonospaceFont,
+    /// Any other token, written as `--name`
+    Custom(String),
+}
+
+impl Token {
+    /// The custom property name without the leading `--`, such as `muted-text`
+    pub fn name(&self) -> &str {
+        match self {
+            Token::Primary => "primary",
+            Token::Secondary => "secondary",
+            Token::Accent => "accent",
+            Token::Background => "background",
+            Token::Surface => "surface",
+            Token::Text => "text",
+            Token::MutedText => "muted-text",
+            Token::Border => "border",
+            Token::Success => "success",
+            Token::Warning => "warning",
+            Token::Danger => "danger",
+            Token::BodyFont => "body-font",
+            Token::HeadingFont => "heading-font",
+            Token::MonospaceFont => "monospace-font",
+            Token::Custom(name) => name,
+        }
+    }
+
+    /// The token of a custom property name without the leading `--`
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "primary" => Token::Primary,
+            "secondary" => Token::Secondary,
+            "accent" => Token::Accent,
+            "background" => Token::Background,
+            "surface" => Token::Surface,
+            "text" => Token::Text,
+            "muted-text" => Token::MutedText,
+            "border" => Token::Border,
+            "success" => Token::Success,
+            "warning" => Token::Warning,
+            "danger" => Token::Danger,
+            "body-font" => Token::BodyFont,
+            "heading-font" => Token::HeadingFont,
+            "monospace-font" => Token::MonospaceFont,
+            name => Token::Custom(name.to_string()),
+        }
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "var(--{})", self.name())
+    }
+}
+
+impl From<Token> for String {
+    fn from(token: Token) -> Self {
+        token.to_string()
+    }
+}
+
+impl TryFrom<String> for Token {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value
+            .strip_prefix("var(--")
+            .and_then(|name| name.strip_suffix(')'))
+            .map(Token::from_name)
+            .ok_or_else(|| format!("expected a token like `var(--name)`, found `{}`", value))
+    }
+}
@@ -2,7 +2,7 @@ use crate::types::{Length, Percentage};
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AutoOrLengthOrPercentage {
     Length(Length),
     Percentage(Percentage),
@@ -41,3 +41,9 @@ impl From<f64> for AutoOrLengthOrPercentage {
         AutoOrLengthOrPercentage::Length(value.into())
     }
 }
+
+impl From<i32> for AutoOrLengthOrPercentage {
+    fn from(value: i32) -> Self {
+        f64::from(value).into()
+    }
+}
@@ -2,7 +2,7 @@ use crate::types::{Length, Percentage};
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LengthOrPercentage {
     Length(Length),
     Percentage(Percentage),
@@ -39,3 +39,9 @@ impl From<f64> for LengthOrPercentage {
         LengthOrPercentage::Length(value.into())
     }
 }
+
+impl From<i32> for LengthOrPercentage {
+    fn from(value: i32) -> Self {
+        f64::from(value).into()
+    }
+}
@@ -0,0 +1,154 @@
+use crate::shape::Shape;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+pub const XHTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+
+/// XHTML markup embedded in a `foreignObject`.
+///
+/// Content of a `foreignObject` is only rendered as HTML when it is in the XHTML namespace,
+/// so every top level element gets an `xmlns` attribute unless it already declares one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Xhtml {
+    markup: String,
+}
+
+/// HTML elements without an end tag, in case the markup does not self-close them
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+impl Xhtml {
+    pub fn new<T: Into<String>>(markup: T) -> Self {
+        Self {
+            markup: markup.into(),
+        }
+    }
+
+    /// Byte offsets just past the tag name of every top level element without an `xmlns`
+    fn namespace_insertion_points(&self) -> Vec<usize> {
+        let markup = self.markup.as_str();
+        let mut points = Vec::new();
+        let mut depth = 0usize;
+        let mut search_from = 0;
+        while let Some(offset) = markup[search_from..].find('<') {
+            let start = search_from + offset + 1;
+            let rest = &markup[start..];
+            // comments, CDATA sections, processing instructions and doctypes
+            let skipped_until = [("!--", "-->"), ("![CDATA[", "]]>"), ("?", "?>"), ("!", ">")]
+                .iter()
+                .find(|(open, _)| rest.starts_with(open))
+                .map(|(_, close)| rest.find(close).map_or(markup.len(), |end| start + end));
+            if let Some(end) = skipped_until {
+                search_from = end;
+                continue;
+            }
+
+            let tag_end = start + tag_length(rest);
+            let tag = &markup[start..tag_end];
+            search_from = tag_end;
+            if let Some(closing) = tag.strip_prefix('/') {
+                if closing
+                    .trim_start()
+                    .starts_with(|c: char| c.is_ascii_alphabetic())
+                {
+                    depth = depth.saturating_sub(1);
+                }
+                continue;
+            }
+            if !tag.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                continue;
+            }
+
+            let name_len = tag
+                .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+                .unwrap_or(tag.len());
+            if depth == 0 && !declares_namespace(&tag[name_len..]) {
+                points.push(start + name_len);
+            }
+            let is_void = VOID_ELEMENTS.contains(&tag[..name_len].to_ascii_lowercase().as_str());
+            if !tag.trim_end_matches('>').ends_with('/') && !is_void {
+                depth += 1;
+            }
+        }
+        points
+    }
+}
+
+/// The length of a tag from after its `<` through its `>`, skipping `>` in quoted values
+fn tag_length(tag: &str) -> usize {
+    let mut quote = None;
+    for (index, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, '>') => return index + 1,
+            _ => {}
+        }
+    }
+    tag.len()
+}
+
+/// Whether the attributes of a start tag include a default namespace `xmlns`
+fn declares_namespace(attributes: &str) -> bool {
+    let mut rest = attributes;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+        let name_len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '=' | '/' | '>'))
+            .unwrap_or(rest.len());
+        if name_len == 0 {
+            return false;
+        }
+        if &rest[..name_len] == "xmlns" {
+            return true;
+        }
+        rest = rest[name_len..].trim_start();
+        if let Some(value) = rest.strip_prefix('=') {
+            let value = value.trim_start();
+            rest = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    value[1..].find(quote).map_or("", |end| &value[end + 2..])
+                }
+                _ => value
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .map_or("", |end| &value[end..]),
+            };
+        }
+    }
+}
+
+impl Display for Xhtml {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut written = 0;
+        for index in self.namespace_insertion_points() {
+            write!(
+                f,
+                "{} xmlns=\"{}\"",
+                &self.markup[written..index],
+                XHTML_NAMESPACE
+            )?;
+            written = index;
+        }
+        write!(f, "{}", &self.markup[written..])
+    }
+}
+
+impl From<&str> for Xhtml {
+    fn from(markup: &str) -> Self {
+        Self::new(markup)
+    }
+}
+
+impl From<String> for Xhtml {
+    fn from(markup: String) -> Self {
+        Self::new(markup)
+    }
+}
+
+impl From<Xhtml> for Shape {
+    fn from(xhtml: Xhtml) -> Self {
+        Self::String(xhtml.to_string())
+    }
+}
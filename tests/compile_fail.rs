@@ -0,0 +1,8 @@
+#[test]
+fn compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/compile_fail/svg_macro/*.rs");
+    cases.compile_fail("tests/compile_fail/valid_child/*.rs");
+    #[cfg(not(feature = "experimental"))]
+    cases.compile_fail("tests/compile_fail/experimental/*.rs");
+}
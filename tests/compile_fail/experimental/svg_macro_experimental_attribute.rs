@@ -0,0 +1,9 @@
+use easy_svg::svg;
+
+fn main() {
+    let _ = svg! {
+        <svg>
+            <rect width=1 height=1 elementtiming="hero"/>
+        </svg>
+    };
+}
@@ -0,0 +1,9 @@
+use easy_svg::svg;
+
+fn main() {
+    let _ = svg! {
+        <svg>
+            <circle r=5 fill=easy_svg::types::Color::Red fill=easy_svg::types::Color::Blue/>
+        </svg>
+    };
+}
@@ -0,0 +1,11 @@
+use easy_svg::svg;
+
+fn main() {
+    let _ = svg! {
+        <svg>
+            <circle r=5>
+                <rect width=1 height=1/>
+            </circle>
+        </svg>
+    };
+}
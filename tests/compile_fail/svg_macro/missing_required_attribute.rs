@@ -0,0 +1,9 @@
+use easy_svg::svg;
+
+fn main() {
+    let _ = svg! {
+        <svg>
+            <rect width=10/>
+        </svg>
+    };
+}
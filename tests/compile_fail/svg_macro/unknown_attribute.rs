@@ -0,0 +1,9 @@
+use easy_svg::svg;
+
+fn main() {
+    let _ = svg! {
+        <svg>
+            <circle r=5 radius=5/>
+        </svg>
+    };
+}
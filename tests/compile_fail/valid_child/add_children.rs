@@ -0,0 +1,5 @@
+use easy_svg::elements::{Circle, Text};
+
+fn main() {
+    let _ = Text::new().add_children([Circle::new(1.), Circle::new(2.)]);
+}
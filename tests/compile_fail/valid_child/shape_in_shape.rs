@@ -0,0 +1,5 @@
+use easy_svg::elements::{Circle, Rect};
+
+fn main() {
+    let _ = Circle::new(5.).add_child(Rect::new(1., 1.));
+}
@@ -0,0 +1,5 @@
+use easy_svg::elements::Svg;
+
+fn main() {
+    let _ = Svg::new().add_child("label".to_string());
+}